@@ -1,5 +1,3 @@
-use std::time::Instant;
-
 use embedded_nano_mesh::{ms, ExactAddressType, Node, NodeConfig, NodeString};
 use proto_lab::{NetworkSimulator, WirelessModemFake};
 
@@ -38,15 +36,17 @@ fn main() {
             .expect("Fail to pack message")
             .into_bytes(),
         ExactAddressType::try_from(2).expect("2 is 0"),
-        2.into(),
+        2,
         false,
     );
 
+    /* Nodes are driven by simulated time, so the run does not depend on machine load. */
+    let clock = simulator.get_clock();
+
     simulator.start_simulation_thread();
 
-    let start_time = Instant::now();
     loop {
-        let current_time = Instant::now().duration_since(start_time).as_millis() as ms;
+        let current_time = clock.get_elapsed_ms() as ms;
 
         let _ = mesh_node_1.update(&mut driver_1, current_time);
         let _ = mesh_node_2.update(&mut driver_2, current_time);
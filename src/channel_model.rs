@@ -0,0 +1,125 @@
+use crate::rng::SimulationRng;
+
+/// Two-state (Gilbert–Elliott) model of the link, which simulates bursty
+/// outages caused by fading. The link is either in good or in bad state,
+/// and each state has it's own probability for the byte to be lost.
+/// The state may change once per tick with given transition probabilities.
+/// See `EtherSimulator::set_link_channel_model`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GilbertElliottModel {
+    /// Probability (0.0 ..= 1.0) of going from good state to bad one at each tick.
+    pub good_to_bad: f64,
+    /// Probability (0.0 ..= 1.0) of going from bad state to good one at each tick.
+    pub bad_to_good: f64,
+    /// Probability (0.0 ..= 1.0) for the byte to be lost while the link is in good state.
+    pub loss_in_good: f64,
+    /// Probability (0.0 ..= 1.0) for the byte to be lost while the link is in bad state.
+    pub loss_in_bad: f64,
+}
+
+/// Model of the link together with it's current state.
+/// Each link starts in good state.
+pub(crate) struct GilbertElliottChannel {
+    model: GilbertElliottModel,
+    is_bad: bool,
+}
+
+impl GilbertElliottChannel {
+    pub(crate) fn new(model: GilbertElliottModel) -> Self {
+        Self {
+            model,
+            is_bad: false,
+        }
+    }
+
+    pub(crate) fn get_model(&self) -> GilbertElliottModel {
+        self.model
+    }
+
+    pub(crate) fn is_bad(&self) -> bool {
+        self.is_bad
+    }
+
+    /// Moves the channel to the next tick.
+    pub(crate) fn step(&mut self, rng: &mut SimulationRng) {
+        let transition = match self.is_bad {
+            true => self.model.bad_to_good,
+            false => self.model.good_to_bad,
+        };
+        if rng.chance(transition) {
+            self.is_bad = !self.is_bad;
+        }
+    }
+
+    /// Returns probability for the byte to be lost in the current state.
+    pub(crate) fn get_loss_probability(&self) -> f64 {
+        match self.is_bad {
+            true => self.model.loss_in_bad,
+            false => self.model.loss_in_good,
+        }
+    }
+}
+
+/// Defines how far the broadcasts of devices reach within the ether, based on
+/// the distance between the devices. Applies only to the pairs of devices,
+/// which both have positions, see `EtherSimulator::set_position`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PropagationModel {
+    /// Distance does not matter, everybody hears everybody.
+    #[default]
+    Unlimited,
+    /// Devices hear each other within given distance.
+    RadioRange(f64),
+    /// Log-distance path loss model. Signal strength at the receiver is
+    /// `tx_power - reference_loss - 10 * exponent * log10(distance)` dBm,
+    /// where `reference_loss` is the loss at the distance of 1 unit.
+    /// Devices hear each other while signal strength is not below `sensitivity`.
+    /// Computed signal strength is also used by `CollisionPolicy::Capture`.
+    LogDistance {
+        tx_power: f32,
+        reference_loss: f32,
+        exponent: f32,
+        sensitivity: f32,
+    },
+}
+
+impl PropagationModel {
+    /// Returns signal strength in dBm at given distance, if the model defines it.
+    /// ```
+    /// use proto_lab::PropagationModel;
+    ///
+    /// let model = PropagationModel::LogDistance {
+    ///     tx_power: 14.0,
+    ///     reference_loss: 40.0,
+    ///     exponent: 2.0,
+    ///     sensitivity: -90.0,
+    /// };
+    /// assert_eq!(model.get_signal_strength(10.0), Some(-46.0));
+    /// assert_eq!(PropagationModel::RadioRange(10.0).get_signal_strength(10.0), None);
+    /// ```
+    pub fn get_signal_strength(&self, distance: f64) -> Option<f32> {
+        match self {
+            PropagationModel::LogDistance {
+                tx_power,
+                reference_loss,
+                exponent,
+                ..
+            } => {
+                let path_loss = 10.0 * exponent * (distance.max(1.0).log10() as f32);
+                Some(tx_power - reference_loss - path_loss)
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks whether the devices hear each other at given distance.
+    pub fn is_in_range(&self, distance: f64) -> bool {
+        match self {
+            PropagationModel::Unlimited => true,
+            PropagationModel::RadioRange(range) => distance <= *range,
+            PropagationModel::LogDistance { sensitivity, .. } => self
+                .get_signal_strength(distance)
+                .is_some_and(|strength| strength >= *sensitivity),
+        }
+    }
+}
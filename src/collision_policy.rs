@@ -0,0 +1,67 @@
+/// Defines how the ether resolves data collision, when multiple devices
+/// are broadcasting at the same tick. Collisions are resolved for each
+/// listening device separately, out of the broadcasts it hears.
+/// Slow transmission of a byte lasts multiple ticks, see `DataRate::TicksPerByte`,
+/// and the byte suffers the collisions of every tick of it's airtime.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum CollisionPolicy {
+    /// Deterministic policy. Among the broadcasting devices, the device which
+    /// has won on the previous tick yields, and the first one of the rest
+    /// by the order of names wins. Device, which has not completed it's byte
+    /// at current tick, never wins, and the byte, which has lost at any tick
+    /// of it's airtime, is dropped.
+    #[default]
+    LastBroadcasterYields,
+    /// Winner is chosen pseudo-randomly among the broadcasting devices.
+    /// As with `LastBroadcasterYields`, the bytes, which are not completed
+    /// or have lost at previous ticks of their airtime, never win.
+    /// The choice is reproducible by the seed of the ether,
+    /// see `EtherSimulator::set_seed`.
+    Random,
+    /// Realistic policy, where nobody wins. When multiple devices are
+    /// broadcasting at the same tick - all the listeners get the byte
+    /// corrupted in the given way. Byte, which is not completed yet,
+    /// is heard as random noise.
+    Destructive(CollisionCorruption),
+    /// Capture effect. The listener hears the strongest broadcast, if it's
+    /// signal exceeds every other one by at least `margin` dB, otherwise
+    /// it sees a collision, corrupted in the given way.
+    /// See `EtherSimulator::set_signal_strength`.
+    Capture {
+        margin: f32,
+        corruption: CollisionCorruption,
+    },
+}
+
+/// Defines what listeners get, when the transmissions are destroyed by collision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionCorruption {
+    /// Listeners get nothing.
+    Silence,
+    /// Listeners get bitwise OR of all the colliding bytes.
+    BitwiseOr,
+    /// Listeners get bitwise XOR of all the colliding bytes.
+    BitwiseXor,
+}
+
+impl CollisionCorruption {
+    /// Returns the bytes, which are heard when `chunks` of bytes collide.
+    /// Chunks collide byte by byte, so the result is as long as the longest chunk.
+    pub(crate) fn corrupt(&self, chunks: &[&[u8]]) -> Option<Vec<u8>> {
+        let length = chunks.iter().map(|chunk| chunk.len()).max()?;
+        let combine = match self {
+            CollisionCorruption::Silence => return None,
+            CollisionCorruption::BitwiseOr => |a: u8, b: u8| a | b,
+            CollisionCorruption::BitwiseXor => |a: u8, b: u8| a ^ b,
+        };
+        let corrupted = (0..length)
+            .map(|index| {
+                chunks
+                    .iter()
+                    .filter_map(|chunk| chunk.get(index).copied())
+                    .fold(0, combine)
+            })
+            .collect();
+        Some(corrupted)
+    }
+}
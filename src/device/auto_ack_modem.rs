@@ -0,0 +1,778 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use super::{DataRate, IODriverSimulator, PacketModemFake};
+use crate::{rng::SimulationRng, SimulatorError};
+
+// Packet radio with hardware auto-acknowledge. Frames are carried by the
+// inner `PacketModemFake`, so they take airtime and get corrupted by overlaps.
+// Each frame on the air starts with the header of auto-ACK protocol:
+//
+//```
+//   +------+--------+-------------+----------+---------+
+//   | kind | source | destination | sequence | payload |
+//   +------+--------+-------------+----------+---------+
+//```
+//
+// Sequence numbers are counted per destination. The receiver drops the data
+// frame as a duplicate only if it is marked as retransmission, and has the
+// same sequence number as the last frame received from the same source.
+//
+// The sender waits for the ACK as long as the exchange takes at the configured
+// data rate, and before each retransmission backs off for a random count of
+// exchanges within the window, which doubles with each attempt, so the senders,
+// which have collided, try again at different times.
+
+const HEADER_SIZE: usize = 4;
+const KIND_DATA: u8 = 0;
+const KIND_ACK: u8 = 1;
+const KIND_RETRANSMISSION: u8 = 2;
+
+/// Count of retransmissions, the modem does by default.
+const DEFAULT_RETRANSMIT_COUNT: u32 = 3;
+
+/// Count of ticks, the modem waits for ACK by default, in addition to the time
+/// the receiver needs to send the ACK back.
+const DEFAULT_RETRANSMIT_DELAY: u64 = 2;
+
+/// Count of attempts, after which the backoff window stops doubling,
+/// like in the binary exponential backoff of Ethernet.
+const MAX_BACKOFF_EXPONENT: u32 = 10;
+
+/// Final status of the frame, sent by `AutoAckModemFake`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxStatus {
+    /// Frame was acknowledged by the destination after given count of attempts.
+    Delivered { attempts: u32 },
+    /// Frame was not acknowledged after all the attempts.
+    Failed { attempts: u32 },
+}
+
+/// Report about the frame, sent by `AutoAckModemFake`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxReport {
+    pub destination: u8,
+    /// Sequence number, which was returned by `AutoAckModemFake::send_frame`.
+    pub sequence: u8,
+    pub status: TxStatus,
+}
+
+#[derive(PartialEq)]
+enum Phase {
+    /// Frame is waiting to be put on the air.
+    Queued,
+    /// Frame is on the air.
+    Sending,
+    /// Frame is sent, and the modem waits for ACK for given count of ticks,
+    /// including the backoff before the retransmission.
+    Waiting(u64),
+}
+
+/// Frame, which is being delivered.
+struct InFlight {
+    destination: u8,
+    sequence: u8,
+    payload: Vec<u8>,
+    attempts: u32,
+    phase: Phase,
+}
+
+struct InternalState {
+    is_in_tick: bool,
+    retransmit_count: u32,
+    retransmit_delay: u64,
+    /// Drives the backoff before retransmissions.
+    rng: SimulationRng,
+    /// Sequence number of the next frame by the destination.
+    next_sequences: BTreeMap<u8, u8>,
+    /// Destination of the bytes, written to the RX pin.
+    pin_destination: Option<u8>,
+    tx_frames: VecDeque<(u8, u8, Vec<u8>)>,
+    in_flight: Option<InFlight>,
+    /// Destinations and sequences of the frames, which are to be acknowledged.
+    pending_acks: VecDeque<(u8, u8)>,
+    /// Sequence of the last received frame by the source, to drop retransmitted duplicates.
+    last_sequences: BTreeMap<u8, u8>,
+    rx_frames: VecDeque<(u8, Vec<u8>)>,
+    tx_reports: VecDeque<TxReport>,
+}
+
+/// Addressed packet radio, which acknowledges received frames, and retransmits
+/// not acknowledged ones, like radios with Enhanced ShockBurst-style auto-ACK.
+/// ```
+/// use proto_lab::{AutoAckModemFake, EtherSimulator, TxReport, TxStatus};
+///
+/// let mut ether = EtherSimulator::new("ether");
+/// let modem_1 = AutoAckModemFake::new("modem_1", 1);
+/// let modem_2 = AutoAckModemFake::new("modem_2", 2);
+/// ether.register_driver(modem_1.clone());
+/// ether.register_driver(modem_2.clone());
+///
+/// let sequence = modem_1.send_frame(2, b"hello").unwrap();
+///
+/// while modem_1.is_busy() {
+///     ether.start_tick();
+///     ether.simulate();
+///     ether.end_tick();
+/// }
+///
+/// assert_eq!(modem_2.receive_frame(), Some((1, b"hello".to_vec())));
+/// assert_eq!(
+///     modem_1.take_tx_report(),
+///     Some(TxReport { destination: 2, sequence, status: TxStatus::Delivered { attempts: 1 } })
+/// );
+/// ```
+pub struct AutoAckModemFake {
+    arc_mutexed_internal_state: Arc<Mutex<InternalState>>,
+    packet_modem: PacketModemFake,
+    address: u8,
+}
+
+impl AutoAckModemFake {
+    pub fn new(name: &str, address: u8) -> Self {
+        Self {
+            arc_mutexed_internal_state: Arc::new(Mutex::new(InternalState {
+                is_in_tick: false,
+                retransmit_count: DEFAULT_RETRANSMIT_COUNT,
+                retransmit_delay: DEFAULT_RETRANSMIT_DELAY,
+                rng: SimulationRng::new(address as u64),
+                next_sequences: BTreeMap::new(),
+                pin_destination: None,
+                tx_frames: VecDeque::new(),
+                in_flight: None,
+                pending_acks: VecDeque::new(),
+                last_sequences: BTreeMap::new(),
+                rx_frames: VecDeque::new(),
+                tx_reports: VecDeque::new(),
+            })),
+            packet_modem: PacketModemFake::new(name),
+            address,
+        }
+    }
+
+    /// Returns the address of the modem.
+    pub fn get_address(&self) -> u8 {
+        self.address
+    }
+
+    /// Queues the frame to be delivered to the modem with `destination` address.
+    /// Returns sequence number of the frame, which identifies it in `TxReport`
+    /// together with the destination. Sequence numbers are counted per destination.
+    pub fn send_frame(&self, destination: u8, payload: &[u8]) -> Result<u8, SimulatorError> {
+        if payload.len() + HEADER_SIZE > u16::MAX as usize {
+            return Err(SimulatorError::FrameTooLong {
+                device: self.packet_modem.get_name().to_owned(),
+                length: payload.len(),
+            });
+        }
+        let mut internal_state = self.lock_internal_state();
+        let next_sequence = internal_state
+            .next_sequences
+            .entry(destination)
+            .or_default();
+        let sequence = *next_sequence;
+        *next_sequence = sequence.wrapping_add(1);
+        internal_state
+            .tx_frames
+            .push_back((destination, sequence, payload.to_vec()));
+        Ok(sequence)
+    }
+
+    /// Takes the oldest of received frames together with the address of it's source.
+    pub fn receive_frame(&self) -> Option<(u8, Vec<u8>)> {
+        self.lock_internal_state().rx_frames.pop_front()
+    }
+
+    /// Takes the oldest report about the frames, which are delivered or failed.
+    pub fn take_tx_report(&self) -> Option<TxReport> {
+        self.lock_internal_state().tx_reports.pop_front()
+    }
+
+    /// Tells whether the modem has frames, which are not yet delivered or failed,
+    /// or acknowledgements, which are not yet sent.
+    pub fn is_busy(&self) -> bool {
+        let internal_state = self.lock_internal_state();
+        internal_state.in_flight.is_some()
+            || !internal_state.tx_frames.is_empty()
+            || !internal_state.pending_acks.is_empty()
+            || self.packet_modem.is_transmitting()
+    }
+
+    /// Sets how many times the not acknowledged frame is sent again.
+    /// ```
+    /// use proto_lab::{AutoAckModemFake, EtherSimulator, TxStatus};
+    ///
+    /// let mut ether = EtherSimulator::new("ether");
+    /// let modem = AutoAckModemFake::new("modem", 1);
+    /// modem.set_retransmit_count(2);
+    /// modem.set_retransmit_delay(5);
+    /// assert_eq!(modem.get_retransmit_count(), 2);
+    /// assert_eq!(modem.get_retransmit_delay(), 5);
+    /// ether.register_driver(modem.clone());
+    ///
+    /// // Nobody acknowledges the frame.
+    /// modem.send_frame(2, b"hello").unwrap();
+    /// while modem.is_busy() {
+    ///     ether.start_tick();
+    ///     ether.simulate();
+    ///     ether.end_tick();
+    /// }
+    ///
+    /// assert_eq!(modem.take_tx_report().unwrap().status, TxStatus::Failed { attempts: 3 });
+    /// ```
+    pub fn set_retransmit_count(&self, count: u32) {
+        self.lock_internal_state().retransmit_count = count;
+    }
+
+    /// Gets how many times the not acknowledged frame is sent again.
+    pub fn get_retransmit_count(&self) -> u32 {
+        self.lock_internal_state().retransmit_count
+    }
+
+    /// Sets how many ticks the modem waits for ACK after the frame is sent,
+    /// in addition to the airtime of the ACK at configured data rate.
+    /// Retransmission is delayed further by the random backoff.
+    pub fn set_retransmit_delay(&self, ticks: u64) {
+        self.lock_internal_state().retransmit_delay = ticks;
+    }
+
+    /// Gets how many ticks the modem waits for ACK in addition to it's airtime.
+    pub fn get_retransmit_delay(&self) -> u64 {
+        self.lock_internal_state().retransmit_delay
+    }
+
+    /// Re-seeds pseudo-random generator of the modem, which drives the backoff
+    /// before retransmissions. By default the modem is seeded by it's address,
+    /// so the modems with different addresses back off differently.
+    pub fn set_seed(&self, seed: u64) {
+        self.lock_internal_state().rng = SimulationRng::new(seed);
+    }
+
+    /// Sets the address, which the bytes written to the RX pin are sent to.
+    /// Without it the modem is not writable through the pins.
+    /// ```
+    /// use proto_lab::{AutoAckModemFake, IODriverSimulator};
+    ///
+    /// let modem = AutoAckModemFake::new("modem", 1);
+    /// assert!(!modem.writable());
+    ///
+    /// modem.set_pin_destination(Some(2));
+    /// assert_eq!(modem.get_pin_destination(), Some(2));
+    /// assert!(modem.writable());
+    /// ```
+    pub fn set_pin_destination(&self, destination: Option<u8>) {
+        self.lock_internal_state().pin_destination = destination;
+    }
+
+    /// Gets the address, which the bytes written to the RX pin are sent to.
+    pub fn get_pin_destination(&self) -> Option<u8> {
+        self.lock_internal_state().pin_destination
+    }
+
+    /// Sets how fast the modem transmits the frames.
+    pub fn set_data_rate(&self, data_rate: DataRate) {
+        self.packet_modem.set_data_rate(data_rate);
+    }
+
+    /// Gets how fast the modem transmits the frames.
+    pub fn get_data_rate(&self) -> DataRate {
+        self.packet_modem.get_data_rate()
+    }
+
+    /// Returns count of frames, which were corrupted on the air, and so were not received.
+    pub fn get_corrupted_frames_count(&self) -> u64 {
+        self.packet_modem.get_corrupted_frames_count()
+    }
+
+    fn lock_internal_state(&self) -> std::sync::MutexGuard<'_, InternalState> {
+        self.arc_mutexed_internal_state.lock().unwrap_or_else(|_| {
+            panic!(
+                "Fail to lock mutex for modem :{}",
+                self.packet_modem.get_name()
+            )
+        })
+    }
+
+    fn send_to_air(&self, kind: u8, destination: u8, sequence: u8, payload: &[u8]) {
+        let mut frame = vec![kind, self.address, destination, sequence];
+        frame.extend_from_slice(payload);
+        self.packet_modem
+            .send_frame(&frame)
+            .unwrap_or_else(|error| panic!("{}", error));
+    }
+
+    /// Returns count of ticks, which pass from the end of the frame till the ACK
+    /// is received: the receiver tells the end of the frame by the silence,
+    /// and sends the ACK back.
+    fn get_ack_timeout(&self, state: &InternalState) -> u64 {
+        self.get_data_rate().get_byte_ticks() as u64
+            + self.packet_modem.get_frame_ticks(HEADER_SIZE)
+            + state.retransmit_delay
+    }
+
+    /// Returns random count of ticks, the modem waits before the retransmission.
+    /// The backoff is a whole count of exchanges, so the senders, which have
+    /// chosen different counts, do not collide again.
+    fn draw_backoff(&self, rng: &mut SimulationRng, in_flight: &InFlight, ack_timeout: u64) -> u64 {
+        let exchange_ticks = self
+            .packet_modem
+            .get_frame_ticks(HEADER_SIZE + in_flight.payload.len())
+            + ack_timeout;
+        let window = 1u64 << in_flight.attempts.min(MAX_BACKOFF_EXPONENT);
+        rng.next_u64() % window * exchange_ticks
+    }
+
+    /// Decides what the modem puts on the air at the starting tick.
+    fn transmit_tick(&self, state: &mut InternalState) {
+        let is_on_air = self.packet_modem.is_transmitting();
+        let ack_timeout = self.get_ack_timeout(state);
+
+        if let Some(in_flight) = state.in_flight.as_mut() {
+            match in_flight.phase {
+                Phase::Sending if !is_on_air => {
+                    let backoff = match in_flight.attempts <= state.retransmit_count {
+                        true => self.draw_backoff(&mut state.rng, in_flight, ack_timeout),
+                        false => 0,
+                    };
+                    in_flight.phase = Phase::Waiting(ack_timeout + backoff)
+                }
+                Phase::Waiting(0) if in_flight.attempts <= state.retransmit_count => {
+                    in_flight.phase = Phase::Queued
+                }
+                Phase::Waiting(0) => {
+                    state.tx_reports.push_back(TxReport {
+                        destination: in_flight.destination,
+                        sequence: in_flight.sequence,
+                        status: TxStatus::Failed {
+                            attempts: in_flight.attempts,
+                        },
+                    });
+                    state.in_flight = None;
+                }
+                Phase::Waiting(ticks) => in_flight.phase = Phase::Waiting(ticks - 1),
+                _ => (),
+            }
+        }
+
+        if is_on_air {
+            return;
+        }
+
+        // Acknowledgements go first.
+        if let Some((destination, sequence)) = state.pending_acks.pop_front() {
+            self.send_to_air(KIND_ACK, destination, sequence, &[]);
+            return;
+        }
+
+        if state.in_flight.is_none() {
+            state.in_flight =
+                state
+                    .tx_frames
+                    .pop_front()
+                    .map(|(destination, sequence, payload)| InFlight {
+                        destination,
+                        sequence,
+                        payload,
+                        attempts: 0,
+                        phase: Phase::Queued,
+                    });
+        }
+
+        if let Some(in_flight) = state.in_flight.as_mut() {
+            if in_flight.phase == Phase::Queued {
+                let kind = match in_flight.attempts {
+                    0 => KIND_DATA,
+                    _ => KIND_RETRANSMISSION,
+                };
+                self.send_to_air(
+                    kind,
+                    in_flight.destination,
+                    in_flight.sequence,
+                    &in_flight.payload,
+                );
+                in_flight.attempts += 1;
+                in_flight.phase = Phase::Sending;
+            }
+        }
+    }
+
+    /// Handles the frames, received at the ending tick.
+    fn receive_tick(&self, state: &mut InternalState) {
+        while let Some(frame) = self.packet_modem.receive_frame() {
+            let [kind, source, destination, sequence] = match frame.get(..HEADER_SIZE) {
+                Some(&[kind, source, destination, sequence]) => {
+                    [kind, source, destination, sequence]
+                }
+                _ => continue,
+            };
+            if destination != self.address {
+                continue;
+            }
+
+            match kind {
+                KIND_DATA | KIND_RETRANSMISSION => {
+                    state.pending_acks.push_back((source, sequence));
+                    let last_sequence = state.last_sequences.insert(source, sequence);
+                    if kind == KIND_DATA || last_sequence != Some(sequence) {
+                        state
+                            .rx_frames
+                            .push_back((source, frame[HEADER_SIZE..].to_vec()));
+                    }
+                }
+                KIND_ACK => {
+                    let is_acknowledged = state.in_flight.as_ref().is_some_and(|in_flight| {
+                        in_flight.destination == source
+                            && in_flight.sequence == sequence
+                            && in_flight.phase != Phase::Queued
+                    });
+                    if let Some(in_flight) = state.in_flight.take_if(|_| is_acknowledged) {
+                        state.tx_reports.push_back(TxReport {
+                            destination: in_flight.destination,
+                            sequence: in_flight.sequence,
+                            status: TxStatus::Delivered {
+                                attempts: in_flight.attempts,
+                            },
+                        });
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+impl Clone for AutoAckModemFake {
+    /// While clonning - method internally shares data for all clonned
+    /// instances of the modem. So all of them can be used in different
+    /// parts of the program, and even in different threads.
+    fn clone(&self) -> Self {
+        AutoAckModemFake {
+            arc_mutexed_internal_state: Arc::clone(&self.arc_mutexed_internal_state),
+            packet_modem: self.packet_modem.clone(),
+            address: self.address,
+        }
+    }
+}
+
+impl IODriverSimulator for AutoAckModemFake {
+    fn get_from_device_network_side(&self) -> Option<u8> {
+        self.packet_modem.get_from_device_network_side()
+    }
+
+    fn put_to_device_network_side(&self, byte: u8) {
+        self.packet_modem.put_to_device_network_side(byte)
+    }
+
+    fn get_bytes_from_device_network_side(&self) -> Vec<u8> {
+        self.packet_modem.get_bytes_from_device_network_side()
+    }
+
+    fn put_bytes_to_device_network_side(&self, bytes: &[u8]) {
+        self.packet_modem.put_bytes_to_device_network_side(bytes)
+    }
+
+    fn put_rssi_to_device_network_side(&self, rssi: f32) {
+        self.packet_modem.put_rssi_to_device_network_side(rssi)
+    }
+
+    fn is_on_air(&self) -> bool {
+        self.packet_modem.is_on_air()
+    }
+
+    /// Yields payload bytes of received frames one by one, skipping empty payloads.
+    /// Prefer `receive_frame`.
+    fn get_from_tx_pin(&self) -> Option<u8> {
+        let mut internal_state = self.lock_internal_state();
+        while internal_state
+            .rx_frames
+            .front()
+            .is_some_and(|(_, payload)| payload.is_empty())
+        {
+            internal_state.rx_frames.pop_front();
+        }
+        let (_, payload) = internal_state.rx_frames.front_mut()?;
+        let byte = payload.first().copied();
+        if payload.len() <= 1 {
+            internal_state.rx_frames.pop_front();
+        } else {
+            payload.remove(0);
+        }
+        byte
+    }
+
+    /// Sends single byte frame to the pin destination. Without the pin destination
+    /// the byte is dropped, see `writable`. Prefer `send_frame`.
+    fn put_to_rx_pin(&self, byte: u8) {
+        if let Some(destination) = self.get_pin_destination() {
+            self.send_frame(destination, &[byte])
+                .unwrap_or_else(|error| panic!("{}", error));
+        }
+    }
+
+    fn start_tick(&self) {
+        let mut internal_state = self.lock_internal_state();
+
+        if !internal_state.is_in_tick {
+            self.transmit_tick(&mut internal_state);
+            self.packet_modem.start_tick();
+            internal_state.is_in_tick = true;
+        }
+    }
+
+    fn end_tick(&self) {
+        let mut internal_state = self.lock_internal_state();
+
+        if internal_state.is_in_tick {
+            self.packet_modem.end_tick();
+            self.receive_tick(&mut internal_state);
+            internal_state.is_in_tick = false;
+        }
+    }
+
+    /// Tells whether there are payload bytes of received frames to be read from the TX pin.
+    fn readable(&self) -> bool {
+        self.lock_internal_state()
+            .rx_frames
+            .iter()
+            .any(|(_, payload)| !payload.is_empty())
+    }
+
+    /// Tells whether bytes can be written to the RX pin,
+    /// which requires the pin destination to be set.
+    fn writable(&self) -> bool {
+        self.get_pin_destination().is_some()
+    }
+
+    fn get_name(&self) -> &str {
+        self.packet_modem.get_name()
+    }
+
+    fn data_rate(&self) -> Option<DataRate> {
+        self.packet_modem.data_rate()
+    }
+}
+
+#[cfg(test)]
+mod auto_ack_modem_device_tests {
+    use super::*;
+    use crate::EtherSimulator;
+
+    fn run_until_idle(ether: &mut EtherSimulator, modem: &AutoAckModemFake) {
+        while modem.is_busy() {
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+        }
+    }
+
+    #[test]
+    fn test_lost_ack_causes_retransmission_without_duplicate() {
+        let mut ether = EtherSimulator::new("ether");
+
+        let sender = AutoAckModemFake::new("sender", 1);
+        let receiver = AutoAckModemFake::new("receiver", 2);
+
+        ether.register_driver(sender.clone());
+        ether.register_driver(receiver.clone());
+
+        // ACKs do not reach the sender.
+        ether.set_reachable("receiver", "sender", false);
+
+        let sequence = sender.send_frame(2, b"hello").unwrap();
+        run_until_idle(&mut ether, &sender);
+
+        assert_eq!(
+            sender.take_tx_report(),
+            Some(TxReport {
+                destination: 2,
+                sequence,
+                status: TxStatus::Failed { attempts: 4 }
+            })
+        );
+        assert_eq!(receiver.receive_frame(), Some((1, b"hello".to_vec())));
+        assert_eq!(receiver.receive_frame(), None);
+
+        // Once ACKs get through, following frames are delivered.
+        ether.set_reachable("receiver", "sender", true);
+        sender.send_frame(2, b"world").unwrap();
+        run_until_idle(&mut ether, &sender);
+
+        assert_eq!(
+            sender.take_tx_report().unwrap().status,
+            TxStatus::Delivered { attempts: 1 }
+        );
+        assert_eq!(receiver.receive_frame(), Some((1, b"world".to_vec())));
+    }
+
+    #[test]
+    fn test_frames_for_other_address_are_ignored() {
+        let mut ether = EtherSimulator::new("ether");
+
+        let sender = AutoAckModemFake::new("sender", 1);
+        let receiver = AutoAckModemFake::new("receiver", 2);
+        let bystander = AutoAckModemFake::new("bystander", 3);
+
+        ether.register_driver(sender.clone());
+        ether.register_driver(receiver.clone());
+        ether.register_driver(bystander.clone());
+
+        sender.send_frame(2, b"hello").unwrap();
+        run_until_idle(&mut ether, &sender);
+
+        assert_eq!(
+            sender.take_tx_report().unwrap().status,
+            TxStatus::Delivered { attempts: 1 }
+        );
+        assert_eq!(receiver.receive_frame(), Some((1, b"hello".to_vec())));
+        assert_eq!(bystander.receive_frame(), None);
+        assert!(!bystander.is_busy());
+    }
+
+    #[test]
+    fn test_sequence_numbers_are_counted_per_destination() {
+        let mut ether = EtherSimulator::new("ether");
+
+        let sender = AutoAckModemFake::new("sender", 1);
+        let receiver_b = AutoAckModemFake::new("receiver_b", 2);
+        let receiver_c = AutoAckModemFake::new("receiver_c", 3);
+
+        ether.register_driver(sender.clone());
+        ether.register_driver(receiver_b.clone());
+        ether.register_driver(receiver_c.clone());
+
+        assert_eq!(sender.send_frame(2, b"first").unwrap(), 0);
+        run_until_idle(&mut ether, &sender);
+
+        // Sequence number towards `receiver_c` wraps around.
+        for _ in 0..256 {
+            sender.send_frame(3, b"c").unwrap();
+        }
+        run_until_idle(&mut ether, &sender);
+
+        assert_eq!(sender.send_frame(2, b"second").unwrap(), 1);
+        run_until_idle(&mut ether, &sender);
+
+        assert_eq!(receiver_b.receive_frame(), Some((1, b"first".to_vec())));
+        assert_eq!(receiver_b.receive_frame(), Some((1, b"second".to_vec())));
+
+        let mut received_by_c = 0;
+        while receiver_c.receive_frame().is_some() {
+            received_by_c += 1;
+        }
+        assert_eq!(received_by_c, 256);
+
+        while let Some(report) = sender.take_tx_report() {
+            assert_eq!(report.status, TxStatus::Delivered { attempts: 1 });
+        }
+    }
+
+    #[test]
+    fn test_pin_writes_need_destination() {
+        let mut ether = EtherSimulator::new("ether");
+
+        let sender = AutoAckModemFake::new("sender", 1);
+        let receiver = AutoAckModemFake::new("receiver", 2);
+
+        ether.register_driver(sender.clone());
+        ether.register_driver(receiver.clone());
+
+        assert!(!sender.writable());
+        sender.set_pin_destination(Some(2));
+        assert!(sender.writable());
+
+        sender.put_to_rx_pin(b'a');
+        sender.put_to_rx_pin(b'b');
+        run_until_idle(&mut ether, &sender);
+
+        assert_eq!(receiver.get_from_tx_pin(), Some(b'a'));
+        assert_eq!(receiver.get_from_tx_pin(), Some(b'b'));
+        assert_eq!(receiver.get_from_tx_pin(), None);
+    }
+
+    #[test]
+    fn test_ack_is_awaited_at_slow_data_rate() {
+        let mut ether = EtherSimulator::new("ether");
+
+        let sender = AutoAckModemFake::new("sender", 1);
+        let receiver = AutoAckModemFake::new("receiver", 2);
+        sender.set_data_rate(DataRate::TicksPerByte(3));
+        receiver.set_data_rate(DataRate::TicksPerByte(3));
+
+        ether.register_driver(sender.clone());
+        ether.register_driver(receiver.clone());
+
+        for payload in [b"hello".as_slice(), b"world"] {
+            sender.send_frame(2, payload).unwrap();
+            run_until_idle(&mut ether, &sender);
+
+            assert_eq!(
+                sender.take_tx_report().unwrap().status,
+                TxStatus::Delivered { attempts: 1 }
+            );
+            assert_eq!(receiver.receive_frame(), Some((1, payload.to_vec())));
+        }
+    }
+
+    #[test]
+    fn test_simultaneous_senders_back_off() {
+        let mut ether = EtherSimulator::new("ether");
+
+        let sender_a = AutoAckModemFake::new("sender_a", 1);
+        let sender_b = AutoAckModemFake::new("sender_b", 3);
+        let receiver = AutoAckModemFake::new("receiver", 2);
+
+        ether.register_driver(sender_a.clone());
+        ether.register_driver(sender_b.clone());
+        ether.register_driver(receiver.clone());
+
+        sender_a.send_frame(2, b"from a").unwrap();
+        sender_b.send_frame(2, b"from b").unwrap();
+        while sender_a.is_busy() || sender_b.is_busy() {
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+        }
+
+        // First attempts collide, and the random backoff separates the retransmissions.
+        for sender in [&sender_a, &sender_b] {
+            let status = sender.take_tx_report().unwrap().status;
+            assert!(matches!(status, TxStatus::Delivered { attempts } if attempts > 1));
+        }
+
+        let mut received = vec![];
+        while let Some(frame) = receiver.receive_frame() {
+            received.push(frame);
+        }
+        received.sort();
+        assert_eq!(
+            received,
+            vec![(1, b"from a".to_vec()), (3, b"from b".to_vec())]
+        );
+        assert!(receiver.get_corrupted_frames_count() > 0);
+    }
+
+    #[test]
+    fn test_empty_payloads_are_skipped_by_pins() {
+        let mut ether = EtherSimulator::new("ether");
+
+        let sender = AutoAckModemFake::new("sender", 1);
+        let receiver = AutoAckModemFake::new("receiver", 2);
+
+        ether.register_driver(sender.clone());
+        ether.register_driver(receiver.clone());
+
+        sender.send_frame(2, b"").unwrap();
+        run_until_idle(&mut ether, &sender);
+        assert!(!receiver.readable());
+        assert_eq!(receiver.get_from_tx_pin(), None);
+
+        sender.send_frame(2, b"").unwrap();
+        sender.send_frame(2, b"x").unwrap();
+        run_until_idle(&mut ether, &sender);
+        assert!(receiver.readable());
+        assert_eq!(receiver.get_from_tx_pin(), Some(b'x'));
+        assert!(!receiver.readable());
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::VecDeque;
+
+/// Defines what the device does, when the byte comes to the full buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// The incoming byte is dropped.
+    #[default]
+    DropNewest,
+    /// The oldest byte of the buffer is dropped to free the space for the incoming one.
+    DropOldest,
+    /// Writer waits until the buffer has free space. Bytes, coming from the
+    /// network, can not be held back, so they are dropped as with `DropNewest`.
+    Block,
+}
+
+/// Byte buffer of the device with optional capacity.
+#[derive(Default)]
+pub(crate) struct BoundedBuffer {
+    bytes: VecDeque<u8>,
+    /// Is never zero, as such buffer could never hold a byte.
+    /// Devices reject zero capacity, before it is set.
+    capacity: Option<usize>,
+    /// Count of bytes, dropped because of overflow.
+    overflow_count: u64,
+}
+
+impl BoundedBuffer {
+    pub(crate) fn get_capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Sets the capacity. Bytes, which do not fit the new capacity, are kept.
+    pub(crate) fn set_capacity(&mut self, capacity: Option<usize>) {
+        self.capacity = capacity;
+    }
+
+    pub(crate) fn get_overflow_count(&self) -> u64 {
+        self.overflow_count
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        self.capacity
+            .is_some_and(|capacity| self.bytes.len() >= capacity)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Puts the byte to the end of the buffer, following the policy on overflow.
+    pub(crate) fn push_back(&mut self, byte: u8, policy: OverflowPolicy) {
+        if self.is_full() {
+            self.overflow_count += 1;
+            match policy {
+                OverflowPolicy::DropNewest | OverflowPolicy::Block => return,
+                OverflowPolicy::DropOldest => {
+                    self.bytes.pop_front();
+                }
+            }
+        }
+        self.bytes.push_back(byte);
+    }
+
+    pub(crate) fn pop_front(&mut self) -> Option<u8> {
+        self.bytes.pop_front()
+    }
+
+    pub(crate) fn get_queue_mut(&mut self) -> &mut VecDeque<u8> {
+        &mut self.bytes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BoundedBuffer, OverflowPolicy};
+
+    #[test]
+    fn test_overflow_policies() {
+        for (policy, expected) in [
+            (OverflowPolicy::DropNewest, vec![1, 2]),
+            (OverflowPolicy::DropOldest, vec![3, 4]),
+            (OverflowPolicy::Block, vec![1, 2]),
+        ] {
+            let mut buffer = BoundedBuffer::default();
+            buffer.set_capacity(Some(2));
+
+            for byte in 1..=4 {
+                buffer.push_back(byte, policy);
+            }
+
+            assert!(buffer.is_full());
+            assert_eq!(buffer.get_overflow_count(), 2);
+            assert_eq!(
+                buffer.get_queue_mut().iter().copied().collect::<Vec<u8>>(),
+                expected
+            );
+        }
+    }
+}
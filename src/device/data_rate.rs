@@ -0,0 +1,159 @@
+use std::collections::VecDeque;
+
+use crate::rng::SimulationRng;
+
+/// Defines how fast the device pushes bytes to the network.
+/// Zero values are treated as 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataRate {
+    /// Device transmits up to given count of bytes each tick.
+    BytesPerTick(u32),
+    /// Transmission of each byte lasts given count of ticks.
+    /// The byte reaches the network at the last tick of it's transmission.
+    TicksPerByte(u32),
+}
+
+impl DataRate {
+    /// Returns the same rate in canonical form, so equal rates compare equal:
+    /// zero values become 1, and one tick per byte becomes one byte per tick.
+    pub(crate) fn normalized(self) -> DataRate {
+        match self {
+            DataRate::BytesPerTick(count) => DataRate::BytesPerTick(count.max(1)),
+            DataRate::TicksPerByte(ticks) if ticks <= 1 => DataRate::BytesPerTick(1),
+            DataRate::TicksPerByte(ticks) => DataRate::TicksPerByte(ticks),
+        }
+    }
+
+    /// Returns count of ticks, the transmission of a single byte lasts.
+    pub(crate) fn get_byte_ticks(self) -> u32 {
+        match self {
+            DataRate::BytesPerTick(_) => 1,
+            DataRate::TicksPerByte(ticks) => ticks.max(1),
+        }
+    }
+
+    /// Returns count of ticks, the transmission of `count` bytes lasts.
+    pub(crate) fn get_airtime(self, count: usize) -> u64 {
+        match self.normalized() {
+            DataRate::BytesPerTick(bytes) => (count as u64).div_ceil(bytes as u64),
+            DataRate::TicksPerByte(ticks) => count as u64 * ticks as u64,
+        }
+    }
+}
+
+impl Default for DataRate {
+    /// One byte per tick.
+    fn default() -> Self {
+        DataRate::BytesPerTick(1)
+    }
+}
+
+/// Defines what the ether or the wire does with the bytes, transferred between
+/// devices with different data rates, see `IODriverSimulator::data_rate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RateMismatchPolicy {
+    /// Data rates are not checked, bytes are transferred as is.
+    #[default]
+    Ignore,
+    /// Bytes are not received at all.
+    Drop,
+    /// Bytes are received as garbage. Garbage is driven by the seeded generator
+    /// of the ether or the wire.
+    Corrupt,
+}
+
+impl RateMismatchPolicy {
+    /// Returns the bytes, which the device with `receiver_rate` receives out of
+    /// the `bytes`, sent by the device with `sender_rate`.
+    /// Devices with unknown data rate receive the bytes as is.
+    pub(crate) fn transfer(
+        &self,
+        sender_rate: Option<DataRate>,
+        receiver_rate: Option<DataRate>,
+        bytes: Vec<u8>,
+        rng: &mut SimulationRng,
+    ) -> Option<Vec<u8>> {
+        let is_mismatch = matches!(
+            (sender_rate, receiver_rate),
+            (Some(sender_rate), Some(receiver_rate))
+                if sender_rate.normalized() != receiver_rate.normalized()
+        );
+        match (is_mismatch, self) {
+            (false, _) | (true, RateMismatchPolicy::Ignore) => Some(bytes),
+            (true, RateMismatchPolicy::Drop) => None,
+            (true, RateMismatchPolicy::Corrupt) => {
+                Some(bytes.iter().map(|_| rng.next_u64() as u8).collect())
+            }
+        }
+    }
+}
+
+/// Paces the bytes, which are sent to the network, according to the data rate.
+#[derive(Default)]
+pub(crate) struct RatePacer {
+    /// Count of ticks, spent on transmission of the current byte.
+    ticks_spent: u32,
+}
+
+impl RatePacer {
+    /// Takes the bytes out of `buffer`, which reach the network at current tick.
+    pub(crate) fn take(&mut self, rate: DataRate, buffer: &mut VecDeque<u8>) -> Vec<u8> {
+        match rate {
+            DataRate::BytesPerTick(count) => {
+                let count = (count.max(1) as usize).min(buffer.len());
+                buffer.drain(..count).collect()
+            }
+            DataRate::TicksPerByte(ticks) => {
+                if buffer.is_empty() {
+                    self.ticks_spent = 0;
+                    return vec![];
+                }
+                self.ticks_spent += 1;
+                if self.ticks_spent < ticks.max(1) {
+                    return vec![];
+                }
+                self.ticks_spent = 0;
+                buffer.pop_front().into_iter().collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DataRate, RateMismatchPolicy};
+    use crate::rng::SimulationRng;
+
+    #[test]
+    fn test_equivalent_rates_do_not_mismatch() {
+        let mut rng = SimulationRng::new(0);
+        let transfer = |sender_rate, receiver_rate, rng: &mut SimulationRng| {
+            RateMismatchPolicy::Drop.transfer(Some(sender_rate), Some(receiver_rate), vec![1], rng)
+        };
+
+        for (sender_rate, receiver_rate) in [
+            (DataRate::BytesPerTick(0), DataRate::BytesPerTick(1)),
+            (DataRate::BytesPerTick(1), DataRate::TicksPerByte(1)),
+            (DataRate::TicksPerByte(0), DataRate::BytesPerTick(1)),
+            (DataRate::TicksPerByte(0), DataRate::TicksPerByte(1)),
+        ] {
+            assert_eq!(
+                transfer(sender_rate, receiver_rate, &mut rng),
+                Some(vec![1])
+            );
+            assert_eq!(
+                transfer(receiver_rate, sender_rate, &mut rng),
+                Some(vec![1])
+            );
+        }
+
+        assert_eq!(
+            transfer(
+                DataRate::BytesPerTick(2),
+                DataRate::TicksPerByte(2),
+                &mut rng
+            ),
+            None
+        );
+    }
+}
@@ -1,8 +1,17 @@
+mod auto_ack_modem;
+mod buffer;
+mod data_rate;
+mod packet_modem;
 mod traits;
-// mod wired_modem;
+mod wired_modem;
 mod wireless_modem;
 
 pub use {
-    traits::IODriverSimulator,
-    /*wired_modem::WiredModemFake,*/ wireless_modem::WirelessModemFake,
+    auto_ack_modem::{AutoAckModemFake, TxReport, TxStatus},
+    buffer::OverflowPolicy,
+    data_rate::{DataRate, RateMismatchPolicy},
+    packet_modem::PacketModemFake,
+    traits::{DriverHandle, IODriverSimulator},
+    wired_modem::WiredModemFake,
+    wireless_modem::WirelessModemFake,
 };
@@ -0,0 +1,601 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use super::{
+    data_rate::{DataRate, RatePacer},
+    IODriverSimulator,
+};
+use crate::SimulatorError;
+
+// Packet radio. Frames are put on the air with the length header in front,
+// and take as many ticks, as needed to send all the bytes at configured
+// data rate. Frames are separated by silence for the time of a byte, so the
+// receiver takes the frame boundaries from the channel: the frame starts when
+// the channel gets busy, and is over when the channel gets silent again.
+// The receiver keeps the frame only if it has sensed no other transmission
+// during the whole frame, and has got exactly as many bytes, as the header says.
+//
+//```
+//                (Network side)
+//                       \|/
+//                        |  - Antenna
+//   +--------------------|-----------------+
+//   | Packet Device      |                 |
+//   |        +-<- [air bytes] <-+          |
+//   |        |                  |          |
+//   |  [reception] ->-+    [tx frames]     |
+//   |                 |         |          |
+//   |           [rx frames]     |          |
+//   |                 |         |          |
+//   |          receive_frame  send_frame   |
+//   +--------------------------------------+
+//```
+
+/// Size of the length header, which precedes each frame on the air.
+const HEADER_SIZE: usize = 2;
+
+enum TickState {
+    InTick,
+    OffTick,
+}
+
+/// Frame, which is being received from the channel.
+#[derive(Default)]
+struct Reception {
+    /// Received bytes, including the header.
+    bytes: Vec<u8>,
+    is_corrupted: bool,
+    /// Count of silent ticks since the channel was busy last time.
+    silent_ticks: u32,
+    /// Whether a chunk, shorter than the data rate, was received.
+    /// Only the last chunk of the frame may be shorter.
+    has_short_chunk: bool,
+}
+
+impl Reception {
+    /// Returns the frame, if exactly all of it's bytes are received.
+    fn get_frame(&self) -> Option<&[u8]> {
+        let header = self.bytes.get(..HEADER_SIZE)?;
+        let length = u16::from_be_bytes([header[0], header[1]]) as usize;
+        (self.bytes.len() == HEADER_SIZE + length).then(|| &self.bytes[HEADER_SIZE..])
+    }
+}
+
+struct InternalState {
+    tick_state: TickState,
+    data_rate: DataRate,
+    rate_pacer: RatePacer,
+    tx_frames: VecDeque<Vec<u8>>,
+    /// Bytes of the frame, which is being transmitted.
+    air_bytes: VecDeque<u8>,
+    /// Count of silent ticks left to be kept before the next frame.
+    gap_ticks_left: u32,
+    /// Whether the modem transmits at current tick.
+    is_on_air: bool,
+    tick_bytes_to_network: Vec<u8>,
+    tick_bytes_from_network: Vec<u8>,
+    /// Count of transmissions of other devices, sensed at current tick.
+    tick_sensed_count: u32,
+    reception: Option<Reception>,
+    rx_frames: VecDeque<Vec<u8>>,
+    /// Count of frames, which were not received because of overlap or loss.
+    corrupted_frames_count: u64,
+}
+
+/// Half-duplex packet radio, which sends and receives whole frames.
+/// Frame occupies the ether for the count of ticks, proportional to it's length,
+/// and any overlap with other transmission corrupts the whole frame.
+pub struct PacketModemFake {
+    arc_mutexed_internal_state: Arc<Mutex<InternalState>>,
+    name: String,
+}
+
+impl PacketModemFake {
+    pub fn new(name: &str) -> Self {
+        Self {
+            arc_mutexed_internal_state: Arc::new(Mutex::new(InternalState {
+                tick_state: TickState::OffTick,
+                data_rate: DataRate::default(),
+                rate_pacer: RatePacer::default(),
+                tx_frames: VecDeque::new(),
+                air_bytes: VecDeque::new(),
+                gap_ticks_left: 0,
+                is_on_air: false,
+                tick_bytes_to_network: vec![],
+                tick_bytes_from_network: vec![],
+                tick_sensed_count: 0,
+                reception: None,
+                rx_frames: VecDeque::new(),
+                corrupted_frames_count: 0,
+            })),
+            name: String::from(name),
+        }
+    }
+
+    /// Queues the frame to be transmitted. Frames are transmitted one by one.
+    /// ```
+    /// use proto_lab::{EtherSimulator, PacketModemFake, SimulatorError};
+    ///
+    /// let mut ether = EtherSimulator::new("ether");
+    /// let modem_1 = PacketModemFake::new("modem_1");
+    /// let modem_2 = PacketModemFake::new("modem_2");
+    /// ether.register_driver(modem_1.clone());
+    /// ether.register_driver(modem_2.clone());
+    ///
+    /// modem_1.send_frame(b"hello").unwrap();
+    /// assert_eq!(
+    ///     modem_1.send_frame(&vec![0; 70000]),
+    ///     Err(SimulatorError::FrameTooLong { device: String::from("modem_1"), length: 70000 })
+    /// );
+    ///
+    /// // 2 bytes of header and 5 bytes of frame take 7 ticks at 1 byte per tick,
+    /// // and the frame is over after a silent tick.
+    /// for _ in 0..8 {
+    ///     assert_eq!(modem_2.receive_frame(), None);
+    ///     ether.start_tick();
+    ///     ether.simulate();
+    ///     ether.end_tick();
+    /// }
+    /// assert_eq!(modem_2.receive_frame(), Some(b"hello".to_vec()));
+    /// ```
+    pub fn send_frame(&self, frame: &[u8]) -> Result<(), SimulatorError> {
+        if frame.len() > u16::MAX as usize {
+            return Err(SimulatorError::FrameTooLong {
+                device: self.name.clone(),
+                length: frame.len(),
+            });
+        }
+        self.lock_internal_state()
+            .tx_frames
+            .push_back(frame.to_vec());
+        Ok(())
+    }
+
+    /// Takes the oldest of received frames.
+    pub fn receive_frame(&self) -> Option<Vec<u8>> {
+        self.lock_internal_state().rx_frames.pop_front()
+    }
+
+    /// Tells whether the modem has frames, which are not yet transmitted.
+    pub fn is_transmitting(&self) -> bool {
+        let internal_state = self.lock_internal_state();
+        !internal_state.air_bytes.is_empty() || !internal_state.tx_frames.is_empty()
+    }
+
+    /// Sets how fast the modem transmits the frames. Frames are separated
+    /// by silence for the time of a single byte at this rate.
+    /// ```
+    /// use proto_lab::{DataRate, IODriverSimulator, PacketModemFake};
+    ///
+    /// let device = PacketModemFake::new("my_modem");
+    /// assert_eq!(device.get_data_rate(), DataRate::BytesPerTick(1));
+    ///
+    /// device.set_data_rate(DataRate::TicksPerByte(2));
+    /// device.send_frame(b"a").unwrap();
+    ///
+    /// let mut on_air = vec![];
+    /// let mut transmitted = vec![];
+    /// for _ in 0..8 {
+    ///     device.start_tick();
+    ///     on_air.push(device.is_on_air());
+    ///     transmitted.extend(device.get_bytes_from_device_network_side());
+    ///     device.end_tick();
+    /// }
+    ///
+    /// // Each byte lasts 2 ticks, and the frame is followed by silence of 2 ticks.
+    /// assert_eq!(on_air, [true, true, true, true, true, true, false, false]);
+    /// assert_eq!(transmitted, [0, 1, b'a']);
+    /// ```
+    pub fn set_data_rate(&self, data_rate: DataRate) {
+        self.lock_internal_state().data_rate = data_rate;
+    }
+
+    /// Gets how fast the modem transmits the frames.
+    pub fn get_data_rate(&self) -> DataRate {
+        self.lock_internal_state().data_rate
+    }
+
+    /// Returns count of ticks, the frame of given length occupies the channel,
+    /// including the silence, which tells the end of the frame.
+    pub(crate) fn get_frame_ticks(&self, length: usize) -> u64 {
+        let data_rate = self.get_data_rate();
+        data_rate.get_airtime(HEADER_SIZE + length) + data_rate.get_byte_ticks() as u64
+    }
+
+    /// Returns count of frames, which were corrupted by overlapping
+    /// transmissions or lost bytes, and so were dropped.
+    pub fn get_corrupted_frames_count(&self) -> u64 {
+        self.lock_internal_state().corrupted_frames_count
+    }
+
+    fn lock_internal_state(&self) -> std::sync::MutexGuard<'_, InternalState> {
+        self.arc_mutexed_internal_state
+            .lock()
+            .unwrap_or_else(|_| panic!("Fail to lock mutex for modem :{}", self.name))
+    }
+}
+
+impl InternalState {
+    /// Takes into account what was heard on the channel at the ending tick.
+    fn receive_tick(&mut self) {
+        let chunk = std::mem::take(&mut self.tick_bytes_from_network);
+        let sensed_count = std::mem::take(&mut self.tick_sensed_count);
+
+        if self.is_on_air {
+            // Half-duplex radio does not hear anything while transmitting.
+            if self.reception.take().is_some() {
+                self.corrupted_frames_count += 1;
+            }
+            return;
+        }
+
+        // Heard bytes tell that the channel is busy even without sensed
+        // transmissions, as wires do not report them.
+        if sensed_count == 0 && chunk.is_empty() {
+            if let Some(reception) = self.reception.as_mut() {
+                reception.silent_ticks += 1;
+                if reception.silent_ticks >= self.data_rate.get_byte_ticks() {
+                    self.complete_reception();
+                }
+            }
+            return;
+        }
+
+        let bytes_per_tick = match self.data_rate.normalized() {
+            DataRate::BytesPerTick(count) => Some(count as usize),
+            DataRate::TicksPerByte(_) => None,
+        };
+        let reception = self.reception.get_or_insert_with(Reception::default);
+        reception.silent_ticks = 0;
+        if sensed_count > 1 || reception.has_short_chunk {
+            reception.is_corrupted = true;
+        }
+        reception.has_short_chunk = bytes_per_tick.is_some_and(|count| chunk.len() < count);
+        reception.bytes.extend(chunk);
+    }
+
+    /// Keeps the frame, which is over, or drops it, if it is corrupted or incomplete.
+    fn complete_reception(&mut self) {
+        let Some(reception) = self.reception.take() else {
+            return;
+        };
+        match reception.get_frame() {
+            Some(frame) if !reception.is_corrupted => self.rx_frames.push_back(frame.to_vec()),
+            _ => self.corrupted_frames_count += 1,
+        }
+    }
+}
+
+impl Clone for PacketModemFake {
+    /// While clonning - method internally shares data for all clonned
+    /// instances of the modem. So all of them can be used in different
+    /// parts of the program, and even in different threads.
+    fn clone(&self) -> Self {
+        PacketModemFake {
+            arc_mutexed_internal_state: Arc::clone(&self.arc_mutexed_internal_state),
+            name: self.name.clone(),
+        }
+    }
+}
+
+impl IODriverSimulator for PacketModemFake {
+    /// Returns first byte of the frame, which goes on the air at current tick.
+    fn get_from_device_network_side(&self) -> Option<u8> {
+        self.get_bytes_from_device_network_side().first().copied()
+    }
+
+    /// Catches a byte from the ether.
+    fn put_to_device_network_side(&self, byte: u8) {
+        self.put_bytes_to_device_network_side(&[byte]);
+    }
+
+    /// Returns bytes of the frame, which go on the air at current tick.
+    fn get_bytes_from_device_network_side(&self) -> Vec<u8> {
+        let internal_state = self.lock_internal_state();
+
+        match internal_state.tick_state {
+            TickState::InTick => internal_state.tick_bytes_to_network.clone(),
+            TickState::OffTick => vec![],
+        }
+    }
+
+    /// Catches the bytes from the ether. Bytes are ignored while transmitting.
+    fn put_bytes_to_device_network_side(&self, bytes: &[u8]) {
+        let mut internal_state = self.lock_internal_state();
+
+        if let TickState::InTick = internal_state.tick_state {
+            if !internal_state.is_on_air && !bytes.is_empty() {
+                internal_state.tick_bytes_from_network = bytes.to_vec();
+            }
+        }
+    }
+
+    /// Counts the transmissions of other devices, to detect overlapping frames.
+    fn put_rssi_to_device_network_side(&self, _rssi: f32) {
+        let mut internal_state = self.lock_internal_state();
+
+        if let TickState::InTick = internal_state.tick_state {
+            internal_state.tick_sensed_count += 1;
+        }
+    }
+
+    /// Tells whether the modem transmits a frame at current tick.
+    fn is_on_air(&self) -> bool {
+        self.lock_internal_state().is_on_air
+    }
+
+    /// Yields bytes of received frames one by one, skipping empty frames.
+    /// Prefer `receive_frame`.
+    fn get_from_tx_pin(&self) -> Option<u8> {
+        let mut internal_state = self.lock_internal_state();
+        while internal_state.rx_frames.front().is_some_and(Vec::is_empty) {
+            internal_state.rx_frames.pop_front();
+        }
+        let frame = internal_state.rx_frames.front_mut()?;
+        let byte = frame.first().copied();
+        if frame.len() <= 1 {
+            internal_state.rx_frames.pop_front();
+        } else {
+            frame.remove(0);
+        }
+        byte
+    }
+
+    /// Sends single byte frame. Prefer `send_frame`.
+    fn put_to_rx_pin(&self, byte: u8) {
+        self.lock_internal_state().tx_frames.push_back(vec![byte]);
+    }
+
+    /// Puts next bytes of the frame on the air.
+    /// ```
+    /// use proto_lab::{DataRate, IODriverSimulator, PacketModemFake};
+    ///
+    /// let device = PacketModemFake::new("my_modem");
+    /// device.set_data_rate(DataRate::BytesPerTick(4));
+    /// device.send_frame(b"abc").unwrap();
+    ///
+    /// device.start_tick();
+    /// assert_eq!(device.get_bytes_from_device_network_side(), [0, 3, b'a', b'b']);
+    /// device.end_tick();
+    ///
+    /// device.start_tick();
+    /// assert_eq!(device.get_bytes_from_device_network_side(), [b'c']);
+    /// device.end_tick();
+    ///
+    /// assert!(!device.is_transmitting());
+    /// ```
+    fn start_tick(&self) {
+        let mut internal_state = self.lock_internal_state();
+
+        match internal_state.tick_state {
+            TickState::OffTick => {
+                let state = &mut *internal_state;
+                if state.air_bytes.is_empty() {
+                    if state.gap_ticks_left > 0 {
+                        state.gap_ticks_left -= 1;
+                    } else if let Some(frame) = state.tx_frames.pop_front() {
+                        state.air_bytes.extend((frame.len() as u16).to_be_bytes());
+                        state.air_bytes.extend(frame);
+                    }
+                }
+                state.is_on_air = !state.air_bytes.is_empty();
+                state.tick_bytes_to_network =
+                    state.rate_pacer.take(state.data_rate, &mut state.air_bytes);
+                if state.is_on_air && state.air_bytes.is_empty() {
+                    // Receivers tell the frames apart by the silence between them.
+                    state.gap_ticks_left = state.data_rate.get_byte_ticks();
+                }
+                state.tick_bytes_from_network.clear();
+                state.tick_sensed_count = 0;
+
+                state.tick_state = TickState::InTick;
+            }
+            TickState::InTick => (),
+        }
+    }
+
+    /// Completes reception of the frames.
+    fn end_tick(&self) {
+        let mut internal_state = self.lock_internal_state();
+
+        match internal_state.tick_state {
+            TickState::OffTick => (),
+            TickState::InTick => {
+                internal_state.receive_tick();
+                internal_state.tick_bytes_to_network.clear();
+                internal_state.is_on_air = false;
+
+                internal_state.tick_state = TickState::OffTick;
+            }
+        }
+    }
+
+    /// Tells whether there are bytes of received frames to be read from the TX pin.
+    fn readable(&self) -> bool {
+        self.lock_internal_state()
+            .rx_frames
+            .iter()
+            .any(|frame| !frame.is_empty())
+    }
+
+    fn writable(&self) -> bool {
+        true
+    }
+
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn data_rate(&self) -> Option<DataRate> {
+        Some(self.get_data_rate())
+    }
+}
+
+#[cfg(test)]
+mod packet_modem_device_tests {
+    use super::*;
+    use crate::EtherSimulator;
+
+    fn run_ticks(ether: &mut EtherSimulator, ticks: usize) {
+        for _ in 0..ticks {
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+        }
+    }
+
+    #[test]
+    fn test_overlapping_frames_are_corrupted() {
+        let mut ether = EtherSimulator::new("ether");
+
+        let modem_1 = PacketModemFake::new("modem_1");
+        let modem_2 = PacketModemFake::new("modem_2");
+        let receiver = PacketModemFake::new("receiver");
+
+        ether.register_driver(modem_1.clone());
+        ether.register_driver(modem_2.clone());
+        ether.register_driver(receiver.clone());
+
+        // Frame of modem_2 starts, while frame of modem_1 is on the air.
+        modem_1.send_frame(b"first").unwrap();
+        run_ticks(&mut ether, 3);
+        modem_2.send_frame(b"second").unwrap();
+        run_ticks(&mut ether, 20);
+
+        assert_eq!(receiver.receive_frame(), None);
+        assert!(receiver.get_corrupted_frames_count() >= 1);
+
+        // Frames, which do not overlap, are received.
+        modem_1.send_frame(b"first").unwrap();
+        run_ticks(&mut ether, 10);
+        modem_2.send_frame(b"second").unwrap();
+        run_ticks(&mut ether, 10);
+
+        assert_eq!(receiver.receive_frame(), Some(b"first".to_vec()));
+        assert_eq!(receiver.receive_frame(), Some(b"second".to_vec()));
+    }
+
+    #[test]
+    fn test_lost_byte_corrupts_frame() {
+        let mut ether = EtherSimulator::new("ether");
+
+        let sender = PacketModemFake::new("sender");
+        let receiver = PacketModemFake::new("receiver");
+        sender.set_data_rate(DataRate::BytesPerTick(3));
+        receiver.set_data_rate(DataRate::BytesPerTick(3));
+
+        ether.register_driver(sender.clone());
+        ether.register_driver(receiver.clone());
+        ether.set_link_loss("sender", "receiver", 0.3);
+
+        // Frames are sent back to back, each takes 4 ticks and the silent tick.
+        for _ in 0..20 {
+            sender.send_frame(b"0123456789").unwrap();
+        }
+        run_ticks(&mut ether, 20 * 5);
+
+        let mut received = 0;
+        while let Some(frame) = receiver.receive_frame() {
+            assert_eq!(frame, b"0123456789");
+            received += 1;
+        }
+        assert_eq!(received + receiver.get_corrupted_frames_count(), 20);
+        assert!(receiver.get_corrupted_frames_count() > 0);
+    }
+
+    #[test]
+    fn test_receiver_keeps_sync_after_dropped_frame() {
+        let mut ether = EtherSimulator::new("ether");
+
+        let sender = PacketModemFake::new("sender");
+        let receiver = PacketModemFake::new("receiver");
+        let late_receiver = PacketModemFake::new("late_receiver");
+
+        ether.register_driver(sender.clone());
+        ether.register_driver(receiver.clone());
+
+        // Each frame takes 12 ticks and the silent tick.
+        for _ in 0..20 {
+            sender.send_frame(b"0123456789").unwrap();
+        }
+
+        // Single byte of the second frame is lost, and the late receiver
+        // starts listening in the middle of the second frame.
+        run_ticks(&mut ether, 13 + 5);
+        ether.set_link_loss("sender", "receiver", 1.0);
+        ether.register_driver(late_receiver.clone());
+        run_ticks(&mut ether, 1);
+        ether.set_link_loss("sender", "receiver", 0.0);
+        run_ticks(&mut ether, 20 * 13);
+
+        for (receiver, expected_count) in [(receiver, 19), (late_receiver, 18)] {
+            let mut received = 0;
+            while let Some(frame) = receiver.receive_frame() {
+                assert_eq!(frame, b"0123456789");
+                received += 1;
+            }
+            assert_eq!(received, expected_count);
+            assert_eq!(receiver.get_corrupted_frames_count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_frames_over_wire() {
+        use crate::WireSimulator;
+
+        let mut wire = WireSimulator::new("wire");
+
+        let modem_1 = PacketModemFake::new("modem_1");
+        let modem_2 = PacketModemFake::new("modem_2");
+        modem_1.set_data_rate(DataRate::TicksPerByte(3));
+        modem_2.set_data_rate(DataRate::TicksPerByte(3));
+
+        wire.connect_driver(modem_1.clone());
+        wire.connect_driver(modem_2.clone());
+
+        // Wire reports no carrier, so frames are told apart by the bytes.
+        for frame in [&b"first"[..], b"second", b"third"] {
+            modem_1.send_frame(frame).unwrap();
+        }
+        for _ in 0..100 {
+            wire.start_tick();
+            wire.simulate();
+            wire.end_tick();
+        }
+
+        assert_eq!(modem_2.receive_frame(), Some(b"first".to_vec()));
+        assert_eq!(modem_2.receive_frame(), Some(b"second".to_vec()));
+        assert_eq!(modem_2.receive_frame(), Some(b"third".to_vec()));
+        assert_eq!(modem_2.get_corrupted_frames_count(), 0);
+    }
+
+    #[test]
+    fn test_empty_frames_are_skipped_by_pins() {
+        let mut ether = EtherSimulator::new("ether");
+
+        let sender = PacketModemFake::new("sender");
+        let receiver = PacketModemFake::new("receiver");
+
+        ether.register_driver(sender.clone());
+        ether.register_driver(receiver.clone());
+
+        sender.send_frame(b"").unwrap();
+        run_ticks(&mut ether, 5);
+        assert!(!receiver.readable());
+        assert_eq!(receiver.get_from_tx_pin(), None);
+
+        sender.send_frame(b"").unwrap();
+        sender.send_frame(b"x").unwrap();
+        run_ticks(&mut ether, 10);
+        assert!(receiver.readable());
+        assert_eq!(receiver.get_from_tx_pin(), Some(b'x'));
+        assert!(!receiver.readable());
+
+        // Frame interface still delivers empty frames.
+        sender.send_frame(b"").unwrap();
+        run_ticks(&mut ether, 5);
+        assert_eq!(receiver.receive_frame(), Some(vec![]));
+    }
+}
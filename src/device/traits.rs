@@ -1,8 +1,35 @@
+use std::sync::Arc;
+
+use super::DataRate;
+
 pub trait IODriverSimulator {
     // Network interfaces
     fn get_from_device_network_side(&self) -> Option<u8>;
     fn put_to_device_network_side(&self, byte: u8);
 
+    // Network interfaces of devices, which transfer more than one byte per tick.
+    // Devices, which transfer single byte per tick, may keep the defaults.
+    fn get_bytes_from_device_network_side(&self) -> Vec<u8> {
+        self.get_from_device_network_side().into_iter().collect()
+    }
+    fn put_bytes_to_device_network_side(&self, bytes: &[u8]) {
+        for byte in bytes {
+            self.put_to_device_network_side(*byte);
+        }
+    }
+
+    // Carrier sense interface. The ether tells the device about each transmission
+    // of other devices within it's reach, with the signal strength in dBm.
+    fn put_rssi_to_device_network_side(&self, _rssi: f32) {}
+
+    // Tells whether the device is transmitting within the current tick. Slow devices
+    // keep the ether busy for the whole airtime of a byte, including the ticks,
+    // when no byte is completed, see `DataRate::TicksPerByte`. Devices, which
+    // are on air only when they emit bytes, may keep the default.
+    fn is_on_air(&self) -> bool {
+        false
+    }
+
     // Device pins interfaces
     fn get_from_tx_pin(&self) -> Option<u8>;
     fn put_to_rx_pin(&self, byte: u8);
@@ -16,4 +43,13 @@ pub trait IODriverSimulator {
     fn writable(&self) -> bool;
 
     fn get_name(&self) -> &str;
+
+    // Data rate, the device is configured to. Devices with unknown data rate
+    // are not affected by `RateMismatchPolicy`.
+    fn data_rate(&self) -> Option<DataRate> {
+        None
+    }
 }
+
+/// Shared handle to any device, which can be registered within the ether.
+pub type DriverHandle = Arc<dyn IODriverSimulator + Send + Sync>;
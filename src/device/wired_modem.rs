@@ -1,6 +1,12 @@
-use std::collections::VecDeque;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
 
-use super::IODriverSimulator;
+use super::{
+    data_rate::{DataRate, RatePacer},
+    IODriverSimulator,
+};
 // Diagram of a full-duplex device, probably modem
 // Is made to picture the idea of internal quques connectivities.
 //
@@ -35,33 +41,122 @@ enum TickState {
     OffTick,
 }
 
-pub struct WiredModemFake {
+struct InternalState {
     from_network_buffer: VecDeque<u8>,
     to_network_buffer: VecDeque<u8>,
-    tick_byte_to_network: Option<u8>,
-    tick_byte_from_network: Option<u8>,
+    tick_bytes_to_network: Vec<u8>,
+    tick_bytes_from_network: Vec<u8>,
     tick_state: TickState,
+    data_rate: DataRate,
+    rate_pacer: RatePacer,
+}
+
+pub struct WiredModemFake {
+    arc_mutexed_internal_state: Arc<Mutex<InternalState>>,
     name: String,
 }
 
 impl WiredModemFake {
     pub fn new(name: &str) -> Self {
         Self {
-            from_network_buffer: VecDeque::new(),
-            to_network_buffer: VecDeque::new(),
-            tick_byte_to_network: None,
-            tick_byte_from_network: None,
-            tick_state: TickState::OffTick,
+            arc_mutexed_internal_state: Arc::new(Mutex::new(InternalState {
+                from_network_buffer: VecDeque::new(),
+                to_network_buffer: VecDeque::new(),
+                tick_bytes_to_network: vec![],
+                tick_bytes_from_network: vec![],
+                tick_state: TickState::OffTick,
+                data_rate: DataRate::default(),
+                rate_pacer: RatePacer::default(),
+            })),
             name: String::from(name),
         }
     }
+
+    pub fn read(&self, buf: &mut [u8]) -> Result<usize, core::convert::Infallible> {
+        let mut count_red: usize = 0;
+        for buf_vancant_place in buf.iter_mut() {
+            if let Some(byte) = self.get_from_tx_pin() {
+                *buf_vancant_place = byte;
+                count_red += 1;
+            }
+        }
+        Ok(count_red)
+    }
+
+    pub fn write(&self, buf: &[u8]) -> Result<usize, core::convert::Infallible> {
+        let mut count_written: usize = 0;
+        for b in buf {
+            self.put_to_rx_pin(*b);
+            count_written += 1;
+        }
+        Ok(count_written)
+    }
+
+    pub fn flush(&self) -> Result<(), core::convert::Infallible> {
+        Ok(())
+    }
+
+    /// Sets how fast the modem transmits the bytes to the wire.
+    /// ```
+    /// use proto_lab::{DataRate, IODriverSimulator, WiredModemFake};
+    ///
+    /// let device = WiredModemFake::new("my_modem");
+    /// device.set_data_rate(DataRate::TicksPerByte(2));
+    /// assert_eq!(device.get_data_rate(), DataRate::TicksPerByte(2));
+    /// device.put_to_rx_pin(b'a');
+    ///
+    /// device.start_tick();
+    /// assert_eq!(device.get_from_device_network_side(), None);
+    /// device.end_tick();
+    ///
+    /// device.start_tick();
+    /// assert_eq!(device.get_from_device_network_side(), Some(b'a'));
+    /// device.end_tick();
+    /// ```
+    pub fn set_data_rate(&self, data_rate: DataRate) {
+        self.lock_internal_state().data_rate = data_rate;
+    }
+
+    /// Gets how fast the modem transmits the bytes to the wire.
+    pub fn get_data_rate(&self) -> DataRate {
+        self.lock_internal_state().data_rate
+    }
+
+    fn lock_internal_state(&self) -> std::sync::MutexGuard<'_, InternalState> {
+        self.arc_mutexed_internal_state
+            .lock()
+            .unwrap_or_else(|_| panic!("Fail to lock mutex for modem :{}", self.name))
+    }
+}
+
+impl Clone for WiredModemFake {
+    /// While clonning - method internally shares data for all clonned
+    /// instances of the modem. So all of them can be used in different
+    /// parts of the program, and even in different threads.
+    /// ```
+    /// use proto_lab::{IODriverSimulator, WiredModemFake};
+    /// let radio_driver = WiredModemFake::new("");
+    /// let radio_driver_clone = radio_driver.clone();
+    ///
+    /// radio_driver.start_tick();
+    /// radio_driver.put_to_device_network_side(b'a');
+    /// radio_driver.end_tick();
+    ///
+    /// assert_eq!(radio_driver_clone.get_from_tx_pin(), Some(b'a'));
+    /// ```
+    fn clone(&self) -> Self {
+        WiredModemFake {
+            arc_mutexed_internal_state: Arc::clone(&self.arc_mutexed_internal_state),
+            name: self.name.clone(),
+        }
+    }
 }
 
 impl IODriverSimulator for WiredModemFake {
     /// Testing to be sent to network
     /// ```
-    /// use network_simulator::{IODriverSimulator, WiredModemFake};
-    /// let mut radio_driver = WiredModemFake::new();
+    /// use proto_lab::{IODriverSimulator, WiredModemFake};
+    /// let radio_driver = WiredModemFake::new("");
     /// radio_driver.start_tick();
     /// assert_eq!(radio_driver.get_from_device_network_side(), None);
     /// radio_driver.end_tick();
@@ -72,16 +167,13 @@ impl IODriverSimulator for WiredModemFake {
     /// assert_eq!(radio_driver.get_from_device_network_side(), Some(b'a'));
     /// radio_driver.end_tick();
     /// ```
-    fn get_from_device_network_side(&mut self) -> Option<u8> {
-        match self.tick_state {
-            TickState::InTick => self.tick_byte_to_network.clone(),
-            TickState::OffTick => None,
-        }
+    fn get_from_device_network_side(&self) -> Option<u8> {
+        self.get_bytes_from_device_network_side().first().copied()
     }
 
     /// ```
-    /// use network_simulator::{IODriverSimulator, WiredModemFake};
-    /// let mut radio_driver = WiredModemFake::new();
+    /// use proto_lab::{IODriverSimulator, WiredModemFake};
+    /// let radio_driver = WiredModemFake::new("");
     /// radio_driver.start_tick();
     /// radio_driver.end_tick();
     /// assert_eq!(radio_driver.get_from_tx_pin(), None);
@@ -97,18 +189,37 @@ impl IODriverSimulator for WiredModemFake {
     /// assert_eq!(radio_driver.get_from_tx_pin(), None);
     /// ```
     /// Testing some data put to queues to be sent to network
-    fn put_to_device_network_side(&mut self, byte: u8) {
-        match self.tick_state {
+    fn put_to_device_network_side(&self, byte: u8) {
+        self.put_bytes_to_device_network_side(&[byte]);
+    }
+
+    /// Bytes, which are sent to the network at current tick.
+    fn get_bytes_from_device_network_side(&self) -> Vec<u8> {
+        let internal_state = self.lock_internal_state();
+
+        match internal_state.tick_state {
+            TickState::InTick => internal_state.tick_bytes_to_network.clone(),
+            TickState::OffTick => vec![],
+        }
+    }
+
+    /// Bytes, which are received from the network at current tick.
+    fn put_bytes_to_device_network_side(&self, bytes: &[u8]) {
+        let mut internal_state = self.lock_internal_state();
+
+        match internal_state.tick_state {
             TickState::InTick => {
-                self.tick_byte_from_network.replace(byte);
+                if !bytes.is_empty() {
+                    internal_state.tick_bytes_from_network = bytes.to_vec();
+                }
             }
             TickState::OffTick => (),
         };
     }
 
     /// ```
-    /// use network_simulator::{IODriverSimulator, WiredModemFake};
-    /// let mut radio_driver = WiredModemFake::new();
+    /// use proto_lab::{IODriverSimulator, WiredModemFake};
+    /// let radio_driver = WiredModemFake::new("");
     /// radio_driver.start_tick();
     /// radio_driver.end_tick();
     /// assert_eq!(radio_driver.get_from_tx_pin(), None);
@@ -124,14 +235,14 @@ impl IODriverSimulator for WiredModemFake {
     /// assert_eq!(radio_driver.get_from_tx_pin(), None);
     /// ```
     /// Testing some data put to queues to be sent to network
-    fn get_from_tx_pin(&mut self) -> Option<u8> {
-        self.from_network_buffer.pop_front()
+    fn get_from_tx_pin(&self) -> Option<u8> {
+        self.lock_internal_state().from_network_buffer.pop_front()
     }
 
     /// Testing to be sent to network
     /// ```
-    /// use network_simulator::{IODriverSimulator, WiredModemFake};
-    /// let mut radio_driver = WiredModemFake::new();
+    /// use proto_lab::{IODriverSimulator, WiredModemFake};
+    /// let radio_driver = WiredModemFake::new("");
     /// radio_driver.start_tick();
     /// assert_eq!(radio_driver.get_from_device_network_side(), None);
     /// radio_driver.end_tick();
@@ -143,13 +254,13 @@ impl IODriverSimulator for WiredModemFake {
     /// radio_driver.end_tick();
     /// ```
     /// Testing some data put to queues to be sent to network
-    fn put_to_rx_pin(&mut self, byte: u8) {
-        self.to_network_buffer.push_back(byte);
+    fn put_to_rx_pin(&self, byte: u8) {
+        self.lock_internal_state().to_network_buffer.push_back(byte);
     }
 
     /// ```
-    /// use network_simulator::{IODriverSimulator, WiredModemFake};
-    /// let mut radio_driver = WiredModemFake::new();
+    /// use proto_lab::{IODriverSimulator, WiredModemFake};
+    /// let radio_driver = WiredModemFake::new("");
     ///
     /// assert_eq!(radio_driver.get_from_device_network_side(), None);
     ///
@@ -163,25 +274,26 @@ impl IODriverSimulator for WiredModemFake {
     /// assert_eq!(radio_driver.get_from_device_network_side(), Some(b'c'));
     /// radio_driver.end_tick();
     /// ```
-    fn start_tick(&mut self) {
-        match self.tick_state {
+    fn start_tick(&self) {
+        let mut internal_state = self.lock_internal_state();
+
+        match internal_state.tick_state {
             TickState::InTick => (),
             TickState::OffTick => {
-                self.tick_byte_to_network = None;
-                self.tick_byte_from_network = None;
+                let state = &mut *internal_state;
+                state.tick_bytes_from_network.clear();
+                state.tick_bytes_to_network = state
+                    .rate_pacer
+                    .take(state.data_rate, &mut state.to_network_buffer);
 
-                if let Some(byte) = self.to_network_buffer.pop_front() {
-                    self.tick_byte_to_network = Some(byte);
-                }
-
-                self.tick_state = TickState::InTick;
+                internal_state.tick_state = TickState::InTick;
             }
         }
     }
 
     /// ```
-    /// use network_simulator::{IODriverSimulator, WiredModemFake};
-    /// let mut radio_driver = WiredModemFake::new();
+    /// use proto_lab::{IODriverSimulator, WiredModemFake};
+    /// let radio_driver = WiredModemFake::new("");
     /// assert_eq!(radio_driver.get_from_device_network_side(), None);
     ///
     /// radio_driver.put_to_rx_pin(b'a');
@@ -194,23 +306,24 @@ impl IODriverSimulator for WiredModemFake {
     /// assert_eq!(radio_driver.get_from_device_network_side(), Some(b'c'));
     /// radio_driver.end_tick();
     /// ```
-    fn end_tick(&mut self) {
-        match self.tick_state {
+    fn end_tick(&self) {
+        let mut internal_state = self.lock_internal_state();
+
+        match internal_state.tick_state {
             TickState::OffTick => (),
             TickState::InTick => {
-                if let Some(byte) = self.tick_byte_from_network.take() {
-                    self.from_network_buffer.push_back(byte);
-                }
-                self.tick_byte_to_network = None;
+                let bytes = std::mem::take(&mut internal_state.tick_bytes_from_network);
+                internal_state.from_network_buffer.extend(bytes);
+                internal_state.tick_bytes_to_network.clear();
 
-                self.tick_state = TickState::OffTick;
+                internal_state.tick_state = TickState::OffTick;
             }
         }
     }
 
     /// ```
-    /// use network_simulator::{IODriverSimulator, WiredModemFake};
-    /// let mut radio_driver = WiredModemFake::new();
+    /// use proto_lab::{IODriverSimulator, WiredModemFake};
+    /// let radio_driver = WiredModemFake::new("");
     /// radio_driver.start_tick();
     /// radio_driver.end_tick();
     /// assert_eq!(radio_driver.readable(), false);
@@ -220,12 +333,12 @@ impl IODriverSimulator for WiredModemFake {
     /// assert_eq!(radio_driver.readable(), true);
     /// ```
     fn readable(&self) -> bool {
-        !self.from_network_buffer.is_empty()
+        !self.lock_internal_state().from_network_buffer.is_empty()
     }
 
     /// ```
-    /// use network_simulator::{IODriverSimulator, WiredModemFake};
-    /// let mut radio_driver = WiredModemFake::new();
+    /// use proto_lab::{IODriverSimulator, WiredModemFake};
+    /// let radio_driver = WiredModemFake::new("");
     /// assert_eq!(radio_driver.writable(), true);
     /// ```
     fn writable(&self) -> bool {
@@ -233,13 +346,24 @@ impl IODriverSimulator for WiredModemFake {
     }
 
     /// ```
-    /// use network_simulator::{IODriverSimulator, WiredModemFake};
-    /// let mut radio_driver = WiredModemFake::new("1");
+    /// use proto_lab::{IODriverSimulator, WiredModemFake};
+    /// let radio_driver = WiredModemFake::new("1");
     /// assert_eq!(radio_driver.get_name(), "1");
     /// ```
     fn get_name(&self) -> &str {
         &self.name
     }
+
+    /// Returns the data rate of the device
+    /// ```
+    /// use proto_lab::{DataRate, IODriverSimulator, WiredModemFake};
+    /// let device = WiredModemFake::new("my_modem");
+    /// device.set_data_rate(DataRate::TicksPerByte(2));
+    /// assert_eq!(device.data_rate(), Some(DataRate::TicksPerByte(2)));
+    /// ```
+    fn data_rate(&self) -> Option<DataRate> {
+        Some(self.get_data_rate())
+    }
 }
 
 impl embedded_io::ErrorType for WiredModemFake {
@@ -253,32 +377,18 @@ impl embedded_io::ReadReady for WiredModemFake {
 }
 
 impl embedded_io::Read for WiredModemFake {
-    // Read from WirelessModuleDriver into buf
     fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
-        let mut count_red: usize = 0;
-        for buf_vancant_place in buf.iter_mut() {
-            if let Some(byte) = self.get_from_tx_pin() {
-                *buf_vancant_place = byte;
-                count_red += 1;
-            }
-        }
-        Ok(count_red)
+        WiredModemFake::read(self, buf)
     }
 }
 
 impl embedded_io::Write for WiredModemFake {
-    // Write from buf into WirelessModuleDriver
     fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
-        let mut count_written: usize = 0;
-        for b in buf {
-            self.put_to_rx_pin(*b);
-            count_written += 1;
-        }
-        Ok(count_written)
+        WiredModemFake::write(self, buf)
     }
 
     fn flush(&mut self) -> Result<(), Self::Error> {
-        Ok(())
+        WiredModemFake::flush(self)
     }
 }
 
@@ -288,7 +398,7 @@ mod wired_modem_device_tests {
 
     #[test]
     fn test_full_duplex_send_per_tick() {
-        let mut modem_device = WiredModemFake::new("");
+        let modem_device = WiredModemFake::new("");
         modem_device.start_tick();
         modem_device.put_to_device_network_side(b'a');
         modem_device.put_to_rx_pin(b'b');
@@ -305,11 +415,24 @@ mod wired_modem_device_tests {
     // Test data collision with overwriting data per same tick
     #[test]
     fn test_data_collision_per_tick() {
-        let mut modem_device = WiredModemFake::new("");
+        let modem_device = WiredModemFake::new("");
         modem_device.start_tick();
         modem_device.put_to_device_network_side(b'a');
         modem_device.put_to_device_network_side(b'b');
         modem_device.end_tick();
         assert_eq!(modem_device.get_from_tx_pin(), Some(b'b'));
     }
+
+    #[test]
+    fn test_send_and_receive_within_same_tick() {
+        let modem_device = WiredModemFake::new("");
+        modem_device.put_to_rx_pin(b'b');
+
+        modem_device.start_tick();
+        assert_eq!(modem_device.get_from_device_network_side(), Some(b'b'));
+        modem_device.put_to_device_network_side(b'a');
+        modem_device.end_tick();
+
+        assert_eq!(modem_device.get_from_tx_pin(), Some(b'a'));
+    }
 }
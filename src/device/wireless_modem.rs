@@ -1,13 +1,22 @@
 use std::{
-    collections::VecDeque,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
-use super::IODriverSimulator;
+use super::{
+    buffer::{BoundedBuffer, OverflowPolicy},
+    data_rate::{DataRate, RatePacer},
+    IODriverSimulator,
+};
+use crate::SimulatorError;
 
 enum AntennaState {
-    Transmit(u8),
-    Receive(u8),
+    /// Bytes, which leave the antenna at current tick. Slow device may keep
+    /// transmitting for multiple ticks before the byte leaves the antenna.
+    Transmit(Vec<u8>),
+    Receive(Vec<u8>),
+    /// Radio switches between receiving and transmitting, so it does neither.
+    Turnaround,
     Idle,
 }
 
@@ -46,9 +55,23 @@ enum TickState {
 
 struct InternalState {
     tick_state: TickState,
-    from_antenna_buffer: VecDeque<u8>,
-    to_antenna_buffer: VecDeque<u8>,
+    from_antenna_buffer: BoundedBuffer,
+    to_antenna_buffer: BoundedBuffer,
+    overflow_policy: OverflowPolicy,
     antennta_state: AntennaState,
+    /// Radio is switched to transmitting, otherwise it is receiving.
+    is_transmitter: bool,
+    turnaround_ticks: u32,
+    /// Count of ticks left till the end of current turnaround.
+    turnaround_ticks_left: u32,
+    /// Count of bytes, which came from the ether while the radio was not receiving.
+    missed_bytes_count: u64,
+    /// Strongest signal, sensed by the antenna at current tick.
+    tick_rssi: Option<f32>,
+    /// Strongest signal, sensed by the antenna at previous tick.
+    last_rssi: Option<f32>,
+    data_rate: DataRate,
+    rate_pacer: RatePacer,
 }
 
 impl embedded_io::ErrorType for WirelessModemFake {
@@ -63,17 +86,23 @@ impl embedded_io::ReadReady for WirelessModemFake {
 
 impl embedded_io::Read for WirelessModemFake {
     fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
-        WirelessModemFake::read(&self, buf)
+        WirelessModemFake::read(self, buf)
+    }
+}
+
+impl embedded_io::WriteReady for WirelessModemFake {
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.writable())
     }
 }
 
 impl embedded_io::Write for WirelessModemFake {
     fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
-        WirelessModemFake::write(&self, buf)
+        WirelessModemFake::write(self, buf)
     }
 
     fn flush(&mut self) -> Result<(), Self::Error> {
-        WirelessModemFake::flush(&self)
+        WirelessModemFake::flush(self)
     }
 }
 
@@ -87,9 +116,18 @@ impl WirelessModemFake {
         WirelessModemFake {
             arc_mutexed_internal_state: Arc::new(Mutex::new(InternalState {
                 tick_state: TickState::OffTick,
-                from_antenna_buffer: VecDeque::new(),
-                to_antenna_buffer: VecDeque::new(),
+                from_antenna_buffer: BoundedBuffer::default(),
+                to_antenna_buffer: BoundedBuffer::default(),
+                overflow_policy: OverflowPolicy::default(),
                 antennta_state: AntennaState::Idle,
+                is_transmitter: false,
+                turnaround_ticks: 0,
+                turnaround_ticks_left: 0,
+                missed_bytes_count: 0,
+                tick_rssi: None,
+                last_rssi: None,
+                data_rate: DataRate::default(),
+                rate_pacer: RatePacer::default(),
             })),
             name: String::from(name),
         }
@@ -106,10 +144,27 @@ impl WirelessModemFake {
         Ok(count_red)
     }
 
+    /// Writes the bytes to the RX pin. With `OverflowPolicy::Block` stops at
+    /// the full TX buffer, or waits for the free space, if nothing is written yet.
+    /// So with blocking policy the modem shall be ticked by other thread.
     pub fn write(&self, buf: &[u8]) -> Result<usize, core::convert::Infallible> {
         let mut count_written: usize = 0;
         for b in buf {
-            self.put_to_rx_pin(*b);
+            // Free space is checked under the same lock the byte is pushed with,
+            // so other writers can not take it in between.
+            let mut internal_state = self.lock_internal_state();
+            while internal_state.overflow_policy == OverflowPolicy::Block
+                && internal_state.to_antenna_buffer.is_full()
+            {
+                if count_written > 0 {
+                    return Ok(count_written);
+                }
+                drop(internal_state);
+                std::thread::sleep(Duration::from_millis(1));
+                internal_state = self.lock_internal_state();
+            }
+            let policy = internal_state.overflow_policy;
+            internal_state.to_antenna_buffer.push_back(*b, policy);
             count_written += 1;
         }
         Ok(count_written)
@@ -119,10 +174,328 @@ impl WirelessModemFake {
         Ok(())
     }
 
+    /// Sets count of ticks, the radio spends switching from receiving to transmitting
+    /// and back. During the turnaround the radio neither transmits, nor receives.
+    /// ```
+    /// use proto_lab::{IODriverSimulator, WirelessModemFake};
+    ///
+    /// let device = WirelessModemFake::new("my_modem");
+    /// device.set_turnaround_ticks(1);
+    /// assert_eq!(device.get_turnaround_ticks(), 1);
+    /// device.put_to_rx_pin(b'a');
+    ///
+    /// device.start_tick();
+    /// assert_eq!(device.get_from_device_network_side(), None);
+    /// device.put_to_device_network_side(b'x');
+    /// device.end_tick();
+    ///
+    /// device.start_tick();
+    /// assert_eq!(device.get_from_device_network_side(), Some(b'a'));
+    /// device.end_tick();
+    ///
+    /// assert_eq!(device.get_missed_bytes_count(), 1);
+    /// ```
+    pub fn set_turnaround_ticks(&self, ticks: u32) {
+        self.lock_internal_state().turnaround_ticks = ticks;
+    }
+
+    /// Gets count of ticks, the radio spends switching from receiving to transmitting and back.
+    pub fn get_turnaround_ticks(&self) -> u32 {
+        self.lock_internal_state().turnaround_ticks
+    }
+
+    /// Returns count of bytes, which came from the ether, while the radio was
+    /// transmitting or turning around, so they were missed.
+    pub fn get_missed_bytes_count(&self) -> u64 {
+        self.lock_internal_state().missed_bytes_count
+    }
+
+    /// Tells whether any other device was transmitting within the reach of
+    /// the modem at previous tick. Is meant for listen-before-talk.
+    /// ```
+    /// use proto_lab::{EtherSimulator, IODriverSimulator, WirelessModemFake};
+    ///
+    /// let mut ether = EtherSimulator::new("ether");
+    /// let modem_1 = WirelessModemFake::new("modem_1");
+    /// let modem_2 = WirelessModemFake::new("modem_2");
+    /// ether.register_driver(modem_1.clone());
+    /// ether.register_driver(modem_2.clone());
+    /// ether.set_signal_strength("modem_1", "modem_2", -60.0);
+    ///
+    /// modem_1.put_to_rx_pin(b'a');
+    /// ether.start_tick();
+    /// ether.simulate();
+    /// ether.end_tick();
+    ///
+    /// assert!(modem_2.channel_busy());
+    /// assert_eq!(modem_2.get_rssi(), Some(-60.0));
+    /// assert!(!modem_1.channel_busy());
+    ///
+    /// ether.start_tick();
+    /// ether.simulate();
+    /// ether.end_tick();
+    ///
+    /// assert!(!modem_2.channel_busy());
+    /// ```
+    pub fn channel_busy(&self) -> bool {
+        self.get_rssi().is_some()
+    }
+
+    /// Returns the strength in dBm of the strongest transmission, sensed
+    /// at previous tick, or `None` if the channel was free.
+    pub fn get_rssi(&self) -> Option<f32> {
+        self.lock_internal_state().last_rssi
+    }
+
+    /// Sets capacity of the buffer of received bytes, which are not yet red
+    /// from the TX pin. `None` stands for unlimited capacity, which is the default.
+    /// ```
+    /// use proto_lab::{IODriverSimulator, WirelessModemFake};
+    ///
+    /// let device = WirelessModemFake::new("my_modem");
+    /// device.set_rx_buffer_capacity(Some(1));
+    /// assert_eq!(device.get_rx_buffer_capacity(), Some(1));
+    ///
+    /// for byte in [b'a', b'b'] {
+    ///     device.start_tick();
+    ///     device.put_to_device_network_side(byte);
+    ///     device.end_tick();
+    /// }
+    ///
+    /// assert_eq!(device.get_rx_overflow_count(), 1);
+    /// assert_eq!(device.get_from_tx_pin(), Some(b'a'));
+    /// assert_eq!(device.get_from_tx_pin(), None);
+    /// ```
+    /// Panics if the capacity is zero.
+    pub fn set_rx_buffer_capacity(&self, capacity: Option<usize>) {
+        self.try_set_rx_buffer_capacity(capacity)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Same as `set_rx_buffer_capacity`, but returns error instead of panicking
+    /// when the capacity is zero.
+    /// ```
+    /// use proto_lab::{SimulatorError, WirelessModemFake};
+    ///
+    /// let device = WirelessModemFake::new("my_modem");
+    /// assert_eq!(
+    ///     device.try_set_rx_buffer_capacity(Some(0)),
+    ///     Err(SimulatorError::ZeroBufferCapacity { device: String::from("my_modem") })
+    /// );
+    /// assert_eq!(device.get_rx_buffer_capacity(), None);
+    /// ```
+    pub fn try_set_rx_buffer_capacity(
+        &self,
+        capacity: Option<usize>,
+    ) -> Result<(), SimulatorError> {
+        self.check_buffer_capacity(capacity)?;
+        self.lock_internal_state()
+            .from_antenna_buffer
+            .set_capacity(capacity);
+        Ok(())
+    }
+
+    /// Gets capacity of the buffer of received bytes.
+    pub fn get_rx_buffer_capacity(&self) -> Option<usize> {
+        self.lock_internal_state()
+            .from_antenna_buffer
+            .get_capacity()
+    }
+
+    /// Sets capacity of the buffer of the bytes, which are written to the RX pin,
+    /// but not yet transmitted. `None` stands for unlimited capacity, which is the default.
+    /// ```
+    /// use proto_lab::{IODriverSimulator, OverflowPolicy, WirelessModemFake};
+    ///
+    /// let device = WirelessModemFake::new("my_modem");
+    /// device.set_tx_buffer_capacity(Some(2));
+    /// device.set_overflow_policy(OverflowPolicy::DropOldest);
+    /// assert_eq!(device.get_tx_buffer_capacity(), Some(2));
+    ///
+    /// device.write(b"abc").unwrap();
+    /// assert!(!device.writable());
+    /// assert_eq!(device.get_tx_overflow_count(), 1);
+    ///
+    /// device.start_tick();
+    /// assert_eq!(device.get_from_device_network_side(), Some(b'b'));
+    /// device.end_tick();
+    /// assert!(device.writable());
+    /// ```
+    /// Panics if the capacity is zero.
+    pub fn set_tx_buffer_capacity(&self, capacity: Option<usize>) {
+        self.try_set_tx_buffer_capacity(capacity)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Same as `set_tx_buffer_capacity`, but returns error instead of panicking
+    /// when the capacity is zero. Blocking write to such buffer would wait forever.
+    pub fn try_set_tx_buffer_capacity(
+        &self,
+        capacity: Option<usize>,
+    ) -> Result<(), SimulatorError> {
+        self.check_buffer_capacity(capacity)?;
+        self.lock_internal_state()
+            .to_antenna_buffer
+            .set_capacity(capacity);
+        Ok(())
+    }
+
+    fn check_buffer_capacity(&self, capacity: Option<usize>) -> Result<(), SimulatorError> {
+        match capacity {
+            Some(0) => Err(SimulatorError::ZeroBufferCapacity {
+                device: self.name.clone(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Gets capacity of the buffer of the bytes to be transmitted.
+    pub fn get_tx_buffer_capacity(&self) -> Option<usize> {
+        self.lock_internal_state().to_antenna_buffer.get_capacity()
+    }
+
+    /// Sets what the modem does with the bytes, coming to the full buffers.
+    pub fn set_overflow_policy(&self, policy: OverflowPolicy) {
+        self.lock_internal_state().overflow_policy = policy;
+    }
+
+    /// Gets what the modem does with the bytes, coming to the full buffers.
+    pub fn get_overflow_policy(&self) -> OverflowPolicy {
+        self.lock_internal_state().overflow_policy
+    }
+
+    /// Returns count of received bytes, dropped because of full buffer.
+    pub fn get_rx_overflow_count(&self) -> u64 {
+        self.lock_internal_state()
+            .from_antenna_buffer
+            .get_overflow_count()
+    }
+
+    /// Returns count of bytes to be transmitted, dropped because of full buffer.
+    pub fn get_tx_overflow_count(&self) -> u64 {
+        self.lock_internal_state()
+            .to_antenna_buffer
+            .get_overflow_count()
+    }
+
+    fn lock_internal_state(&self) -> std::sync::MutexGuard<'_, InternalState> {
+        self.arc_mutexed_internal_state
+            .lock()
+            .unwrap_or_else(|_| panic!("Fail to lock mutex for modem :{}", self.name))
+    }
+
+    /// Sets how fast the modem transmits the bytes to the ether.
+    /// ```
+    /// use proto_lab::{DataRate, IODriverSimulator, WirelessModemFake};
+    ///
+    /// let device = WirelessModemFake::new("my_modem");
+    /// assert_eq!(device.get_data_rate(), DataRate::BytesPerTick(1));
+    ///
+    /// device.set_data_rate(DataRate::BytesPerTick(3));
+    /// device.write(b"hello").unwrap();
+    ///
+    /// device.start_tick();
+    /// assert_eq!(device.get_bytes_from_device_network_side(), b"hel");
+    /// device.end_tick();
+    ///
+    /// device.start_tick();
+    /// assert_eq!(device.get_bytes_from_device_network_side(), b"lo");
+    /// device.end_tick();
+    /// ```
+    pub fn set_data_rate(&self, data_rate: DataRate) {
+        self.lock_internal_state().data_rate = data_rate;
+    }
+
+    /// Gets how fast the modem transmits the bytes to the ether.
+    pub fn get_data_rate(&self) -> DataRate {
+        self.lock_internal_state().data_rate
+    }
+
+    /// Same as `IODriverSimulator::get_from_device_network_side`, but returns
+    /// error instead of panicking when the device is not within the tick.
+    /// ```
+    /// use proto_lab::{SimulatorError, WirelessModemFake};
+    ///
+    /// let device = WirelessModemFake::new("my_modem");
+    /// assert_eq!(
+    ///     device.try_get_from_device_network_side(),
+    ///     Err(SimulatorError::DeviceNotInTick { device: String::from("my_modem") })
+    /// );
+    /// ```
+    pub fn try_get_from_device_network_side(&self) -> Result<Option<u8>, SimulatorError> {
+        self.try_get_bytes_from_device_network_side()
+            .map(|bytes| bytes.first().copied())
+    }
+
+    /// Same as `IODriverSimulator::get_bytes_from_device_network_side`, but returns
+    /// error instead of panicking when the device is not within the tick.
+    pub fn try_get_bytes_from_device_network_side(&self) -> Result<Vec<u8>, SimulatorError> {
+        let locked_internal_state = self
+            .arc_mutexed_internal_state
+            .lock()
+            .unwrap_or_else(|_| panic!("Fail to lock mutex for modem :{}", self.name));
+
+        match locked_internal_state.tick_state {
+            TickState::OffTick => Err(SimulatorError::DeviceNotInTick {
+                device: self.name.clone(),
+            }),
+            TickState::InTick => match &locked_internal_state.antennta_state {
+                AntennaState::Transmit(bytes) => Ok(bytes.clone()),
+                _ => Ok(vec![]),
+            },
+        }
+    }
+
+    /// Same as `IODriverSimulator::put_to_device_network_side`, but returns
+    /// error instead of panicking when the device is not within the tick.
+    /// ```
+    /// use proto_lab::{SimulatorError, WirelessModemFake};
+    ///
+    /// let device = WirelessModemFake::new("my_modem");
+    /// assert_eq!(
+    ///     device.try_put_to_device_network_side(1),
+    ///     Err(SimulatorError::DeviceNotInTick { device: String::from("my_modem") })
+    /// );
+    /// ```
+    pub fn try_put_to_device_network_side(&self, byte: u8) -> Result<(), SimulatorError> {
+        self.try_put_bytes_to_device_network_side(&[byte])
+    }
+
+    /// Same as `IODriverSimulator::put_bytes_to_device_network_side`, but returns
+    /// error instead of panicking when the device is not within the tick.
+    pub fn try_put_bytes_to_device_network_side(&self, bytes: &[u8]) -> Result<(), SimulatorError> {
+        let mut locked_internal_state = self
+            .arc_mutexed_internal_state
+            .lock()
+            .unwrap_or_else(|_| panic!("Fail to lock mutex for modem :{}", self.name));
+
+        match locked_internal_state.tick_state {
+            TickState::OffTick => Err(SimulatorError::DeviceNotInTick {
+                device: self.name.clone(),
+            }),
+            TickState::InTick => {
+                match locked_internal_state.antennta_state {
+                    AntennaState::Transmit(_) | AntennaState::Turnaround => {
+                        locked_internal_state.missed_bytes_count += bytes.len() as u64;
+                    }
+                    AntennaState::Idle | AntennaState::Receive(_) => {
+                        if !bytes.is_empty() {
+                            locked_internal_state.antennta_state =
+                                AntennaState::Receive(bytes.to_vec())
+                        }
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Clone for WirelessModemFake {
     /// While clonning - method internally shares data for all clonned
     /// instances of the modem. So all of them can be used in different
     /// parts of the program, and even in different threads.
-    pub fn clone(&self) -> Self {
+    fn clone(&self) -> Self {
         WirelessModemFake {
             arc_mutexed_internal_state: Arc::clone(&self.arc_mutexed_internal_state),
             name: self.name.clone(),
@@ -147,20 +520,9 @@ impl IODriverSimulator for WirelessModemFake {
     /// assert_eq!(device.get_from_device_network_side(), Some(1));
     /// device.end_tick();
     /// ```
-
     fn get_from_device_network_side(&self) -> Option<u8> {
-        let locked_internal_state = self
-            .arc_mutexed_internal_state
-            .lock()
-            .expect(format!("Fail to lock mutex for modem :{}", self.name).as_str());
-
-        match locked_internal_state.tick_state {
-            TickState::OffTick => panic!("Impossible to put_to_device_network_side. Device not in simulation mode. Simulation is within the tick. You shall start tick first."),
-            TickState::InTick => match locked_internal_state.antennta_state {
-                AntennaState::Transmit(byte) => Some(byte),
-                _ => None,
-            },
-        }
+        self.try_get_from_device_network_side()
+            .unwrap_or_else(|error| panic!("{}", error))
     }
 
     /// Simulates that the modem caught a byte from the ether
@@ -176,22 +538,45 @@ impl IODriverSimulator for WirelessModemFake {
     /// assert_eq!(device.get_from_tx_pin(), Some(1));
     /// ```
     fn put_to_device_network_side(&self, byte: u8) {
-        let mut locked_internal_state = self
-            .arc_mutexed_internal_state
-            .lock()
-            .expect(format!("Fail to lock mutex for modem :{}", self.name).as_str());
+        self.try_put_to_device_network_side(byte)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
 
-        match locked_internal_state.tick_state {
-            TickState::OffTick => panic!("Impossible to put_to_device_network_side. Device not in simulation mode. Simulation is within the tick. You shall start tick first."),
-            TickState::InTick => match locked_internal_state.antennta_state {
-                AntennaState::Transmit(_) => (),
-                AntennaState::Idle | AntennaState::Receive(_) => {
-                    locked_internal_state.antennta_state = AntennaState::Receive(byte)
-                }
-            },
+    /// Simulates that the modem senses transmission of other device.
+    /// Only the strongest transmission of the tick is kept.
+    fn put_rssi_to_device_network_side(&self, rssi: f32) {
+        let mut internal_state = self.lock_internal_state();
+
+        if let TickState::InTick = internal_state.tick_state {
+            internal_state.tick_rssi = Some(
+                internal_state
+                    .tick_rssi
+                    .map_or(rssi, |tick_rssi| tick_rssi.max(rssi)),
+            );
         }
     }
 
+    /// Tells whether the modem transmits within the current tick, even if no byte
+    /// is completed at this tick.
+    fn is_on_air(&self) -> bool {
+        matches!(
+            self.lock_internal_state().antennta_state,
+            AntennaState::Transmit(_)
+        )
+    }
+
+    /// Simulates that the modem emits the bytes of current tick towards the ether.
+    fn get_bytes_from_device_network_side(&self) -> Vec<u8> {
+        self.try_get_bytes_from_device_network_side()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Simulates that the modem caught the bytes of current tick from the ether.
+    fn put_bytes_to_device_network_side(&self, bytes: &[u8]) {
+        self.try_put_bytes_to_device_network_side(bytes)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
     /// Reads a byte on the TX pin
     /// ```
     /// use proto_lab::WirelessModemFake;
@@ -208,7 +593,7 @@ impl IODriverSimulator for WirelessModemFake {
         let mut locked_internal_state = self
             .arc_mutexed_internal_state
             .lock()
-            .expect(format!("Fail to lock mutex for modem :{}", self.name).as_str());
+            .unwrap_or_else(|_| panic!("Fail to lock mutex for modem :{}", self.name));
 
         locked_internal_state.from_antenna_buffer.pop_front()
     }
@@ -227,9 +612,12 @@ impl IODriverSimulator for WirelessModemFake {
         let mut locked_internal_state = self
             .arc_mutexed_internal_state
             .lock()
-            .expect(format!("Fail to lock mutex for modem :{}", self.name).as_str());
+            .unwrap_or_else(|_| panic!("Fail to lock mutex for modem :{}", self.name));
 
-        locked_internal_state.to_antenna_buffer.push_back(byte);
+        let policy = locked_internal_state.overflow_policy;
+        locked_internal_state
+            .to_antenna_buffer
+            .push_back(byte, policy);
     }
 
     /// Tick is needed only for simulating time during which ineraction with the ether is going.
@@ -238,15 +626,32 @@ impl IODriverSimulator for WirelessModemFake {
         let mut locked_internal_state = self
             .arc_mutexed_internal_state
             .lock()
-            .expect(format!("Fail to lock mutex for modem :{}", self.name).as_str());
+            .unwrap_or_else(|_| panic!("Fail to lock mutex for modem :{}", self.name));
 
         match locked_internal_state.tick_state {
             TickState::OffTick => {
-                locked_internal_state.antennta_state =
-                    match locked_internal_state.to_antenna_buffer.pop_front() {
-                        Some(byte) => AntennaState::Transmit(byte),
-                        _ => AntennaState::Idle,
-                    };
+                let state = &mut *locked_internal_state;
+                let is_transmitting = !state.to_antenna_buffer.is_empty();
+
+                if state.turnaround_ticks_left == 0
+                    && state.turnaround_ticks > 0
+                    && is_transmitting != state.is_transmitter
+                {
+                    state.turnaround_ticks_left = state.turnaround_ticks;
+                }
+                state.is_transmitter = is_transmitting;
+
+                state.antennta_state = if state.turnaround_ticks_left > 0 {
+                    state.turnaround_ticks_left -= 1;
+                    AntennaState::Turnaround
+                } else if is_transmitting {
+                    let bytes = state
+                        .rate_pacer
+                        .take(state.data_rate, state.to_antenna_buffer.get_queue_mut());
+                    AntennaState::Transmit(bytes)
+                } else {
+                    AntennaState::Idle
+                };
 
                 locked_internal_state.tick_state = TickState::InTick;
             }
@@ -260,19 +665,25 @@ impl IODriverSimulator for WirelessModemFake {
         let mut locked_internal_state = self
             .arc_mutexed_internal_state
             .lock()
-            .expect(format!("Fail to lock mutex for modem :{}", self.name).as_str());
+            .unwrap_or_else(|_| panic!("Fail to lock mutex for modem :{}", self.name));
 
         match locked_internal_state.tick_state {
             TickState::OffTick => (),
             TickState::InTick => {
-                match locked_internal_state.antennta_state {
-                    AntennaState::Receive(byte) => {
-                        locked_internal_state.from_antenna_buffer.push_back(byte);
+                let antenna_state = std::mem::replace(
+                    &mut locked_internal_state.antennta_state,
+                    AntennaState::Idle,
+                );
+                if let AntennaState::Receive(bytes) = antenna_state {
+                    let policy = locked_internal_state.overflow_policy;
+                    for byte in bytes {
+                        locked_internal_state
+                            .from_antenna_buffer
+                            .push_back(byte, policy);
                     }
-                    _ => (),
                 }
 
-                locked_internal_state.antennta_state = AntennaState::Idle;
+                locked_internal_state.last_rssi = locked_internal_state.tick_rssi.take();
 
                 locked_internal_state.tick_state = TickState::OffTick;
             }
@@ -295,19 +706,19 @@ impl IODriverSimulator for WirelessModemFake {
         let locked_internal_state = self
             .arc_mutexed_internal_state
             .lock()
-            .expect(format!("Fail to lock mutex for modem :{}", self.name).as_str());
+            .unwrap_or_else(|_| panic!("Fail to lock mutex for modem :{}", self.name));
 
         !locked_internal_state.from_antenna_buffer.is_empty()
     }
 
-    /// Tells if the device is ready to be written in
+    /// Tells if the device has free space in the buffer of bytes to be transmitted
     /// ```
     /// use proto_lab::WirelessModemFake;
     /// use proto_lab::IODriverSimulator;
     /// assert!(WirelessModemFake::new("").writable());
     /// ```
     fn writable(&self) -> bool {
-        true
+        !self.lock_internal_state().to_antenna_buffer.is_full()
     }
 
     /// Returns the name of the device
@@ -320,6 +731,17 @@ impl IODriverSimulator for WirelessModemFake {
     fn get_name(&self) -> &str {
         &self.name
     }
+
+    /// Returns the data rate of the device
+    /// ```
+    /// use proto_lab::{DataRate, IODriverSimulator, WirelessModemFake};
+    /// let device = WirelessModemFake::new("my_modem");
+    /// device.set_data_rate(DataRate::TicksPerByte(2));
+    /// assert_eq!(device.data_rate(), Some(DataRate::TicksPerByte(2)));
+    /// ```
+    fn data_rate(&self) -> Option<DataRate> {
+        Some(self.get_data_rate())
+    }
 }
 
 #[cfg(test)]
@@ -353,4 +775,136 @@ mod radio_modem_device_tests {
         modem_device.end_tick();
         assert_eq!(modem_device.get_from_tx_pin(), Some(b'c'));
     }
+
+    #[test]
+    fn test_slow_modem_is_busy_while_transmitting() {
+        let modem_device = WirelessModemFake::new("");
+        modem_device.set_data_rate(DataRate::TicksPerByte(3));
+        modem_device.put_to_rx_pin(b'a');
+
+        let mut transmitted = vec![];
+        for _ in 0..4 {
+            modem_device.start_tick();
+            transmitted.push(modem_device.get_from_device_network_side());
+            modem_device.put_to_device_network_side(b'b');
+            modem_device.end_tick();
+        }
+
+        assert_eq!(transmitted, vec![None, None, Some(b'a'), None]);
+        // Half-duplex modem hears the ether only after the transmission is over.
+        assert_eq!(modem_device.get_from_tx_pin(), Some(b'b'));
+        assert_eq!(modem_device.get_from_tx_pin(), None);
+    }
+
+    #[test]
+    fn test_blocking_write_waits_for_free_space() {
+        let modem_device = WirelessModemFake::new("");
+        modem_device.set_tx_buffer_capacity(Some(2));
+        modem_device.set_overflow_policy(OverflowPolicy::Block);
+
+        // Write stops at the full buffer.
+        assert_eq!(modem_device.write(b"abc"), Ok(2));
+        assert_eq!(modem_device.get_tx_overflow_count(), 0);
+
+        let ticking_modem = modem_device.clone();
+        let ticking_thread = std::thread::spawn(move || {
+            let mut transmitted = vec![];
+            while transmitted.len() < 3 {
+                ticking_modem.start_tick();
+                transmitted.extend(ticking_modem.get_from_device_network_side());
+                ticking_modem.end_tick();
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            transmitted
+        });
+
+        // Write waits until the ticking thread frees the space.
+        assert_eq!(modem_device.write(b"c"), Ok(1));
+        assert_eq!(ticking_thread.join().unwrap(), b"abc");
+    }
+
+    #[test]
+    fn test_concurrent_blocking_writes_lose_nothing() {
+        let modem_device = WirelessModemFake::new("");
+        modem_device.set_tx_buffer_capacity(Some(1));
+        modem_device.set_overflow_policy(OverflowPolicy::Block);
+
+        let writers: Vec<_> = (0..2)
+            .map(|_| {
+                let writing_modem = modem_device.clone();
+                std::thread::spawn(move || {
+                    let mut written = 0;
+                    while written < 50 {
+                        written += writing_modem.write(&[0; 50][written..]).unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        // Ticks until all the bytes are transmitted, or the writers are done
+        // with nothing left to transmit.
+        let mut transmitted = 0;
+        while transmitted < 100
+            && !(writers.iter().all(|writer| writer.is_finished())
+                && modem_device
+                    .lock_internal_state()
+                    .to_antenna_buffer
+                    .is_empty())
+        {
+            modem_device.start_tick();
+            transmitted += modem_device.get_bytes_from_device_network_side().len();
+            modem_device.end_tick();
+        }
+
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        assert_eq!(transmitted, 100);
+        assert_eq!(modem_device.get_tx_overflow_count(), 0);
+    }
+
+    #[test]
+    fn test_turnaround_between_transmitting_and_receiving() {
+        let modem_device = WirelessModemFake::new("");
+        modem_device.set_turnaround_ticks(2);
+        modem_device.put_to_rx_pin(b'a');
+
+        let mut transmitted = vec![];
+        for byte in b"123456" {
+            modem_device.start_tick();
+            transmitted.push(modem_device.get_from_device_network_side());
+            modem_device.put_to_device_network_side(*byte);
+            modem_device.end_tick();
+        }
+
+        // RX -> TX turnaround, transmission, TX -> RX turnaround, receiving.
+        assert_eq!(transmitted, vec![None, None, Some(b'a'), None, None, None]);
+        assert_eq!(modem_device.get_missed_bytes_count(), 5);
+        assert_eq!(modem_device.get_from_tx_pin(), Some(b'6'));
+        assert_eq!(modem_device.get_from_tx_pin(), None);
+    }
+
+    #[test]
+    fn test_zero_buffer_capacity_is_rejected() {
+        let modem_device = WirelessModemFake::new("modem");
+        modem_device.set_tx_buffer_capacity(Some(1));
+        modem_device.set_overflow_policy(OverflowPolicy::Block);
+
+        let error = SimulatorError::ZeroBufferCapacity {
+            device: String::from("modem"),
+        };
+        assert_eq!(
+            modem_device.try_set_tx_buffer_capacity(Some(0)),
+            Err(error.clone())
+        );
+        assert_eq!(modem_device.try_set_rx_buffer_capacity(Some(0)), Err(error));
+
+        // Previous capacities are kept, so blocking write does not hang.
+        assert_eq!(modem_device.get_tx_buffer_capacity(), Some(1));
+        assert_eq!(modem_device.get_rx_buffer_capacity(), None);
+        assert_eq!(modem_device.write(b"ab"), Ok(1));
+
+        let result = std::panic::catch_unwind(|| modem_device.set_tx_buffer_capacity(Some(0)));
+        assert!(result.is_err());
+    }
 }
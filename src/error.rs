@@ -0,0 +1,70 @@
+use std::fmt;
+
+/// Errors, which can happen during the simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimulatorError {
+    /// The operation is not possible while the simulation thread is running.
+    SimulationThreadAlreadyStarted,
+    /// The operation requires the simulation thread to be running.
+    SimulationThreadNotStarted,
+    /// The simulation thread has panicked.
+    SimulationThreadPanicked,
+    /// The operation requires the simulation thread to be paused.
+    SimulationThreadNotPaused,
+    /// The device was accessed from network side while it is not within the tick.
+    DeviceNotInTick { device: String },
+    /// Both ends of the wire are already connected.
+    WireFullyConnected { wire: String },
+    /// The awaited condition was not met within the given amount of ticks.
+    Timeout { ticks: u64 },
+    /// The frame does not fit the length field of the packet modem.
+    FrameTooLong { device: String, length: usize },
+    /// Buffer of the device can not have zero capacity, as it could never hold a byte.
+    ZeroBufferCapacity { device: String },
+    /// Mobility model of the device has invalid parameters, see `MobilityModel`.
+    InvalidMobilityModel { device: String },
+}
+
+impl fmt::Display for SimulatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimulatorError::SimulationThreadAlreadyStarted => write!(
+                f,
+                "Simulation thread is already started. Can not do it while thread is running"
+            ),
+            SimulatorError::SimulationThreadNotStarted => {
+                write!(f, "Simulation thread is not started")
+            }
+            SimulatorError::SimulationThreadPanicked => {
+                write!(f, "Simulation thread has panicked")
+            }
+            SimulatorError::SimulationThreadNotPaused => {
+                write!(f, "Simulation thread is not paused")
+            }
+            SimulatorError::DeviceNotInTick { device } => write!(
+                f,
+                "Device {} is not in simulation mode. Simulation is within the tick. You shall start tick first.",
+                device
+            ),
+            SimulatorError::WireFullyConnected { wire } => {
+                write!(f, "Wire {} already connects two devices", wire)
+            }
+            SimulatorError::Timeout { ticks } => {
+                write!(f, "Condition was not met within {} ticks", ticks)
+            }
+            SimulatorError::FrameTooLong { device, length } => write!(
+                f,
+                "Frame of {} bytes is too long to be sent by device {}",
+                length, device
+            ),
+            SimulatorError::ZeroBufferCapacity { device } => {
+                write!(f, "Buffer of device {} can not have zero capacity", device)
+            }
+            SimulatorError::InvalidMobilityModel { device } => {
+                write!(f, "Mobility model of device {} is invalid", device)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SimulatorError {}
@@ -1,14 +1,341 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     sync::{Arc, Mutex},
 };
 
-use crate::{device::IODriverSimulator, WirelessModemFake};
+use crate::{
+    channel_model::GilbertElliottChannel,
+    device::{DriverHandle, IODriverSimulator},
+    mobility::Node,
+    rng::SimulationRng,
+    CollisionPolicy, DataRate, GilbertElliottModel, MobilityModel, Position, PropagationModel,
+    RateMismatchPolicy, SimulatorError,
+};
+
+/// Seed of the ether, which was not seeded explicitly.
+const DEFAULT_SEED: u64 = 0;
+
+/// Signal strength of the link, which was not configured explicitly.
+const DEFAULT_SIGNAL_STRENGTH: f32 = 0.0;
+
+/// Configuration of directed link from one device to another within the ether.
+#[derive(Default)]
+struct LinkConfig {
+    /// Receiver does not hear the sender at all.
+    unreachable: bool,
+    signal_strength: Option<f32>,
+    loss_probability: f64,
+    bit_error_rate: Option<f64>,
+    channel: Option<GilbertElliottChannel>,
+    /// Count of ticks the byte travels from sender to receiver.
+    delay_ticks: u64,
+}
+
+/// Bytes, which are on their way from sender to receiver over the delayed link.
+struct Delivery {
+    sender: String,
+    receiver: String,
+    /// `None` if the transmission is only sensed, but it's bytes are not received.
+    bytes: Option<Vec<u8>>,
+}
+
+/// Interference, suffered by the byte on it's way from sender to receiver.
+/// Slow transmission is heard for multiple ticks before the byte is completed,
+/// so the interference is collected over the whole airtime of the byte.
+#[derive(Default)]
+struct Interference {
+    /// Bytes of other transmissions, completed during the airtime of the byte.
+    chunks: Vec<Vec<u8>>,
+    /// The byte overlapped with other transmission, which byte was not completed.
+    is_noisy: bool,
+}
+
+/// Configuration and state of simulation, shared by all the clones of the ether.
+struct EtherState {
+    collision_policy: CollisionPolicy,
+    rate_mismatch_policy: RateMismatchPolicy,
+    rng: SimulationRng,
+    /// Bit error rate of links, which do not override it.
+    bit_error_rate: f64,
+    /// Directed links, identified by (sender, receiver) names.
+    links: BTreeMap<(String, String), LinkConfig>,
+    propagation_model: PropagationModel,
+    /// Positions of devices, identified by names.
+    nodes: BTreeMap<String, Node>,
+    /// Name of the device, which was heard by each listener on the previous tick.
+    last_heard_devices: BTreeMap<String, String>,
+    /// Random priorities of the broadcasting devices at current tick.
+    priorities: BTreeMap<String, u64>,
+    /// Count of ticks simulated by the ether.
+    tick: u64,
+    /// Bytes travelling over delayed links, by the tick of their arrival.
+    in_flight: BTreeMap<u64, Vec<Delivery>>,
+    /// Interference of the bytes on air, by (receiver, sender) names.
+    interference: BTreeMap<(String, String), Interference>,
+}
+
+impl EtherState {
+    fn get_link(&self, sender: &str, receiver: &str) -> Option<&LinkConfig> {
+        self.links.get(&(sender.to_owned(), receiver.to_owned()))
+    }
+
+    fn get_link_mut(&mut self, sender: &str, receiver: &str) -> &mut LinkConfig {
+        self.links
+            .entry((sender.to_owned(), receiver.to_owned()))
+            .or_default()
+    }
+
+    /// Returns distance between the devices, if both of them have positions.
+    fn get_distance(&self, sender: &str, receiver: &str) -> Option<f64> {
+        let sender = self.nodes.get(sender)?.get_position();
+        let receiver = self.nodes.get(receiver)?.get_position();
+        Some(sender.distance_to(&receiver))
+    }
+
+    fn is_reachable(&self, sender: &str, receiver: &str) -> bool {
+        let is_enabled = self
+            .get_link(sender, receiver)
+            .is_none_or(|link| !link.unreachable);
+        let is_in_range = self
+            .get_distance(sender, receiver)
+            .is_none_or(|distance| self.propagation_model.is_in_range(distance));
+        is_enabled && is_in_range
+    }
+
+    /// Removes links, position and bytes in flight of the device,
+    /// so the device registered later under the same name starts clean.
+    fn remove_device(&mut self, device: &str) {
+        self.links
+            .retain(|(sender, receiver), _| sender != device && receiver != device);
+        self.nodes.remove(device);
+        self.last_heard_devices
+            .retain(|listener, sender| listener != device && sender != device);
+        self.priorities.remove(device);
+        for deliveries in self.in_flight.values_mut() {
+            deliveries.retain(|delivery| delivery.sender != device && delivery.receiver != device);
+        }
+        self.in_flight
+            .retain(|_, deliveries| !deliveries.is_empty());
+        self.interference
+            .retain(|(receiver, sender), _| receiver != device && sender != device);
+    }
+
+    /// Moves all the devices with mobility models to the next tick.
+    fn step_nodes(&mut self) {
+        for node in self.nodes.values_mut() {
+            node.step(&mut self.rng);
+        }
+    }
+
+    /// Decides whether the byte broadcasted by `sender` is lost on it's way to `receiver`.
+    /// Independent loss of the link is combined with the loss of it's channel model.
+    fn is_lost(&mut self, sender: &str, receiver: &str) -> bool {
+        let loss_probability = self.get_link(sender, receiver).map_or(0.0, |link| {
+            let channel_loss = link
+                .channel
+                .as_ref()
+                .map_or(0.0, GilbertElliottChannel::get_loss_probability);
+            1.0 - (1.0 - link.loss_probability) * (1.0 - channel_loss)
+        });
+        self.rng.chance(loss_probability)
+    }
+
+    /// Moves channel models of all the links to the next tick.
+    fn step_channels(&mut self) {
+        for link in self.links.values_mut() {
+            if let Some(channel) = link.channel.as_mut() {
+                channel.step(&mut self.rng);
+            }
+        }
+    }
+
+    fn get_bit_error_rate(&self, sender: &str, receiver: &str) -> f64 {
+        self.get_link(sender, receiver)
+            .and_then(|link| link.bit_error_rate)
+            .unwrap_or(self.bit_error_rate)
+    }
+
+    /// Flips bits of the byte, broadcasted by `sender`, on it's way to `receiver`.
+    fn apply_bit_errors(&mut self, sender: &str, receiver: &str, byte: u8) -> u8 {
+        let bit_error_rate = self.get_bit_error_rate(sender, receiver);
+        self.rng.flip_bits(byte, bit_error_rate)
+    }
+
+    /// Explicitly configured strength of the link takes precedence over
+    /// the strength, computed by the propagation model.
+    fn get_signal_strength(&self, sender: &str, receiver: &str) -> f32 {
+        self.get_link(sender, receiver)
+            .and_then(|link| link.signal_strength)
+            .or_else(|| {
+                self.get_distance(sender, receiver)
+                    .and_then(|distance| self.propagation_model.get_signal_strength(distance))
+            })
+            .unwrap_or(DEFAULT_SIGNAL_STRENGTH)
+    }
+
+    /// With `CollisionPolicy::Random` - each broadcasting device gets random priority
+    /// once per tick, so all the listeners, which hear same broadcasts, hear the same winner.
+    fn draw_priorities(&mut self, senders: &BTreeSet<&str>) {
+        self.priorities.clear();
+        if self.collision_policy == CollisionPolicy::Random && senders.len() > 1 {
+            for sender in senders.iter() {
+                let priority = self.rng.next_u64();
+                self.priorities.insert((*sender).to_owned(), priority);
+            }
+        }
+    }
+
+    fn get_link_delay(&self, sender: &str, receiver: &str) -> u64 {
+        self.get_link(sender, receiver)
+            .map_or(0, |link| link.delay_ticks)
+    }
+
+    /// Chooses the broadcast, which survives the collision at current tick, if any.
+    /// Transmission without completed byte never wins arbitration, and neither does
+    /// the byte, which was interfered at the previous ticks of it's airtime,
+    /// except for the capture effect, which depends on the signal strength only.
+    fn choose_winner<'a>(
+        &self,
+        receiver: &str,
+        heard: &[(&'a str, Vec<u8>)],
+        completed: &[(&'a str, &[u8], Option<Interference>)],
+    ) -> Option<&'a str> {
+        let candidates = completed
+            .iter()
+            .filter(|(_, _, interference)| interference.is_none())
+            .map(|(sender, _, _)| *sender);
+        match self.collision_policy {
+            // Deterministic simulation of data collision
+            // In case when multiple devices are broadasting at the same time -
+            // clears out data of device which had broadcast on the previous
+            // iteration of simulation.
+            CollisionPolicy::LastBroadcasterYields => {
+                let last_heard = self.last_heard_devices.get(receiver).map(String::as_str);
+                let candidates: Vec<&str> = candidates.collect();
+                candidates
+                    .iter()
+                    .find(|sender| Some(**sender) != last_heard)
+                    .or(candidates.first())
+                    .copied()
+            }
+            CollisionPolicy::Random => candidates.max_by_key(|sender| self.priorities.get(*sender)),
+            CollisionPolicy::Destructive(_) => None,
+            CollisionPolicy::Capture { margin, .. } => {
+                let mut by_strength: Vec<(f32, &str)> = heard
+                    .iter()
+                    .map(|(sender, _)| (self.get_signal_strength(sender, receiver), *sender))
+                    .collect();
+                by_strength.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+                (by_strength[0].0 - by_strength[1].0 >= margin).then_some(by_strength[0].1)
+            }
+        }
+    }
+
+    /// Returns what is heard out of the colliding `chunks` according to the policy.
+    /// Transmissions, which bytes are not completed yet, are heard as random noise,
+    /// if `is_noisy`. With the policies, which choose a winner, nothing is heard.
+    fn corrupt(&mut self, mut chunks: Vec<Vec<u8>>, is_noisy: bool) -> Option<Vec<u8>> {
+        let corruption = match self.collision_policy {
+            CollisionPolicy::Destructive(corruption)
+            | CollisionPolicy::Capture { corruption, .. } => corruption,
+            CollisionPolicy::LastBroadcasterYields | CollisionPolicy::Random => return None,
+        };
+        if is_noisy {
+            let length = chunks.iter().map(Vec::len).max()?;
+            let noise = (0..length)
+                .map(|_| (self.rng.next_u64() % 255) as u8 + 1)
+                .collect();
+            chunks.push(noise);
+        }
+        let chunks: Vec<&[u8]> = chunks.iter().map(Vec::as_slice).collect();
+        corruption.corrupt(&chunks)
+    }
+
+    /// Decides what the `receiver` hears out of the `heard` broadcasts, which
+    /// are ordered by the names of senders. Empty broadcast stands for transmission,
+    /// which is on air, but has not completed a byte at current tick.
+    /// Byte, which overlaps with other transmission at any tick of it's airtime,
+    /// is garbled or dropped, unless it has won every collision.
+    fn resolve_collision(&mut self, receiver: &str, heard: &[(&str, Vec<u8>)]) -> Option<Vec<u8>> {
+        // Transmissions, which went off air, do not have a byte to be interfered.
+        self.interference.retain(|(listener, sender), _| {
+            listener != receiver || heard.iter().any(|(heard_sender, _)| heard_sender == sender)
+        });
+
+        let completed: Vec<(&str, &[u8], Option<Interference>)> = heard
+            .iter()
+            .filter(|(_, bytes)| !bytes.is_empty())
+            .map(|(sender, bytes)| {
+                let key = (receiver.to_owned(), (*sender).to_owned());
+                (*sender, bytes.as_slice(), self.interference.remove(&key))
+            })
+            .collect();
+
+        let winner = match heard {
+            [] => None,
+            [(single, _)] => Some(*single),
+            _ => self.choose_winner(receiver, heard, &completed),
+        };
+
+        // Bytes in the middle of their airtime suffer the collision of current tick.
+        for (sender, _) in heard
+            .iter()
+            .filter(|(sender, bytes)| bytes.is_empty() && Some(*sender) != winner)
+        {
+            let interference = self
+                .interference
+                .entry((receiver.to_owned(), (*sender).to_owned()))
+                .or_default();
+            for (_, bytes) in heard.iter().filter(|(other, _)| other != sender) {
+                if bytes.is_empty() {
+                    interference.is_noisy = true;
+                } else {
+                    interference.chunks.push(bytes.clone());
+                }
+            }
+        }
+
+        match winner {
+            Some(winner) => {
+                self.last_heard_devices
+                    .insert(receiver.to_owned(), winner.to_owned());
+                let (_, bytes, interference) = completed
+                    .into_iter()
+                    .find(|(sender, _, _)| *sender == winner)?;
+                match interference {
+                    None => Some(bytes.to_vec()),
+                    Some(interference) => {
+                        let mut chunks = vec![bytes.to_vec()];
+                        chunks.extend(interference.chunks);
+                        self.corrupt(chunks, interference.is_noisy)
+                    }
+                }
+            }
+            None => {
+                self.last_heard_devices.remove(receiver);
+                let mut chunks = vec![];
+                let mut is_noisy = heard.iter().any(|(_, bytes)| bytes.is_empty());
+                for (_, bytes, interference) in completed {
+                    chunks.push(bytes.to_vec());
+                    if let Some(interference) = interference {
+                        chunks.extend(interference.chunks);
+                        is_noisy |= interference.is_noisy;
+                    }
+                }
+                self.corrupt(chunks, is_noisy)
+            }
+        }
+    }
+}
 
 pub struct EtherSimulator {
     name: String,
-    devices: Arc<Mutex<Vec<WirelessModemFake>>>,
-    last_broadcasted_device: Option<String>,
+    devices: Arc<Mutex<Vec<DriverHandle>>>,
+    /// Devices which take part in the current tick. Devices registered
+    /// or unregistered in the middle of the tick are affected from the next tick.
+    tick_devices: Mutex<Option<Vec<DriverHandle>>>,
+    state: Arc<Mutex<EtherState>>,
 }
 
 impl EtherSimulator {
@@ -16,10 +343,28 @@ impl EtherSimulator {
         Self {
             name: String::from(name),
             devices: Arc::new(Mutex::new(vec![])),
-            last_broadcasted_device: None,
+            tick_devices: Mutex::new(None),
+            state: Arc::new(Mutex::new(EtherState {
+                collision_policy: CollisionPolicy::default(),
+                rate_mismatch_policy: RateMismatchPolicy::default(),
+                rng: SimulationRng::new(DEFAULT_SEED),
+                bit_error_rate: 0.0,
+                links: BTreeMap::new(),
+                propagation_model: PropagationModel::default(),
+                nodes: BTreeMap::new(),
+                last_heard_devices: BTreeMap::new(),
+                priorities: BTreeMap::new(),
+                tick: 0,
+                in_flight: BTreeMap::new(),
+                interference: BTreeMap::new(),
+            })),
         }
     }
 
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, EtherState> {
+        self.state.lock().expect("Fail to get lock on ether state")
+    }
+
     /// Gets the name of the ether
     /// ```
     /// use proto_lab::EtherSimulator;
@@ -30,184 +375,763 @@ impl EtherSimulator {
         &self.name
     }
 
-    /// Registers a new device (driver / modem)
+    /// Sets the policy of resolving data collisions.
     /// ```
-    /// use proto_lab::EtherSimulator;
-    /// use proto_lab::WirelessModemFake;
-    /// use proto_lab::IODriverSimulator;
+    /// use proto_lab::{CollisionPolicy, EtherSimulator};
     ///
     /// let mut ether = EtherSimulator::new("my_ether");
-    /// ether.register_driver(WirelessModemFake::new("my_modem"));
-    /// assert_eq!(ether.get_driver("my_modem").unwrap().get_name(), "my_modem");
+    /// assert_eq!(ether.get_collision_policy(), CollisionPolicy::LastBroadcasterYields);
+    /// ether.set_collision_policy(CollisionPolicy::Random);
+    /// assert_eq!(ether.get_collision_policy(), CollisionPolicy::Random);
     /// ```
-    pub fn register_driver(&mut self, driver: WirelessModemFake) {
-        let mut devices = self.devices.lock().expect("Fail to get lock on devices");
-        devices.push(WirelessModemFake::clone(&driver));
+    pub fn set_collision_policy(&mut self, policy: CollisionPolicy) {
+        self.lock_state().collision_policy = policy;
+    }
+
+    /// Gets the policy of resolving data collisions.
+    pub fn get_collision_policy(&self) -> CollisionPolicy {
+        self.lock_state().collision_policy.clone()
     }
 
-    /// Unregisters a device
+    /// Sets what happens with the bytes, transferred between the devices
+    /// with different data rates.
     /// ```
-    /// use proto_lab::EtherSimulator;
-    /// use proto_lab::WirelessModemFake;
-    /// use proto_lab::IODriverSimulator;
+    /// use proto_lab::{DataRate, EtherSimulator, IODriverSimulator, RateMismatchPolicy, WirelessModemFake};
     ///
     /// let mut ether = EtherSimulator::new("my_ether");
-    /// ether.register_driver(WirelessModemFake::new("my_modem"));
-    /// ether.unregister_driver("my_modem");
-    /// assert!(ether.get_driver("my_modem").is_none());
+    /// ether.set_rate_mismatch_policy(RateMismatchPolicy::Drop);
+    /// assert_eq!(ether.get_rate_mismatch_policy(), RateMismatchPolicy::Drop);
+    ///
+    /// let modem_1 = WirelessModemFake::new("modem_1");
+    /// let modem_2 = WirelessModemFake::new("modem_2");
+    /// modem_2.set_data_rate(DataRate::BytesPerTick(2));
+    /// ether.register_driver(modem_1.clone());
+    /// ether.register_driver(modem_2.clone());
+    ///
+    /// modem_1.put_to_rx_pin(b'a');
+    /// ether.start_tick();
+    /// ether.simulate();
+    /// ether.end_tick();
+    /// assert_eq!(modem_2.get_from_tx_pin(), None);
     /// ```
-    pub fn unregister_driver(&mut self, name: &str) {
-        let mut devices = self.devices.lock().expect("Fail to get lock on devices");
+    pub fn set_rate_mismatch_policy(&mut self, policy: RateMismatchPolicy) {
+        self.lock_state().rate_mismatch_policy = policy;
+    }
 
-        loop {
-            let mut index_to_remove: Option<_> = None;
+    /// Gets what happens with the bytes, transferred between the devices
+    /// with different data rates.
+    pub fn get_rate_mismatch_policy(&self) -> RateMismatchPolicy {
+        self.lock_state().rate_mismatch_policy
+    }
 
-            for (i, device) in devices.iter_mut().enumerate() {
-                if device.get_name() == name {
-                    index_to_remove.replace(i);
-                }
-            }
+    /// Re-seeds pseudo-random generator of the ether, which drives all the
+    /// random decisions of the ether. Same seed and same scenario
+    /// give the same simulation result.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.lock_state().rng = SimulationRng::new(seed);
+    }
 
-            match index_to_remove {
-                Some(i) => devices.remove(i),
-                None => break,
-            };
-        }
+    /// Sets whether `receiver` hears `sender`. By default all the devices of the
+    /// ether hear each other, unless they are out of range, see
+    /// `set_propagation_model`. Reachability is directed, so asymmetric links
+    /// and hidden-terminal topologies can be built within the single ether.
+    /// ```
+    /// use proto_lab::{EtherSimulator, IODriverSimulator, WirelessModemFake};
+    ///
+    /// let mut ether = EtherSimulator::new("my_ether");
+    ///
+    /// let modem_a = WirelessModemFake::new("a");
+    /// let modem_b = WirelessModemFake::new("b");
+    /// let modem_c = WirelessModemFake::new("c");
+    /// ether.register_driver(modem_a.clone());
+    /// ether.register_driver(modem_b.clone());
+    /// ether.register_driver(modem_c.clone());
+    ///
+    /// // Both `a` and `c` hear `b`, but not each other.
+    /// ether.set_reachable("a", "c", false);
+    /// ether.set_reachable("c", "a", false);
+    /// assert!(!ether.is_reachable("a", "c"));
+    /// assert!(ether.is_reachable("a", "b"));
+    ///
+    /// modem_a.put_to_rx_pin(b'a');
+    /// ether.start_tick();
+    /// ether.simulate();
+    /// ether.end_tick();
+    ///
+    /// assert_eq!(modem_b.get_from_tx_pin(), Some(b'a'));
+    /// assert_eq!(modem_c.get_from_tx_pin(), None);
+    /// ```
+    pub fn set_reachable(&mut self, sender: &str, receiver: &str, reachable: bool) {
+        self.lock_state().get_link_mut(sender, receiver).unreachable = !reachable;
     }
 
-    /// Gets a registered device
+    /// Checks whether `receiver` hears `sender`.
+    pub fn is_reachable(&self, sender: &str, receiver: &str) -> bool {
+        self.lock_state().is_reachable(sender, receiver)
+    }
+
+    /// Sets the model, which decides whether the devices hear each other
+    /// by the distance between them. See `set_position`.
+    /// Explicitly unreachable links stay unreachable regardless of the distance.
     /// ```
-    /// use proto_lab::EtherSimulator;
-    /// use proto_lab::WirelessModemFake;
-    /// use proto_lab::IODriverSimulator;
+    /// use proto_lab::{EtherSimulator, Position, PropagationModel};
     ///
     /// let mut ether = EtherSimulator::new("my_ether");
-    /// assert!(ether.get_driver("my_modem").is_none());
-    /// ether.register_driver(WirelessModemFake::new("my_modem"));
-    /// assert_eq!(ether.get_driver("my_modem").unwrap().get_name(), "my_modem");
+    /// ether.set_propagation_model(PropagationModel::RadioRange(10.0));
+    /// assert_eq!(ether.get_propagation_model(), PropagationModel::RadioRange(10.0));
+    ///
+    /// ether.set_position("a", Position::new(0.0, 0.0, 0.0));
+    /// ether.set_position("b", Position::new(6.0, 8.0, 0.0));
+    /// ether.set_position("c", Position::new(12.0, 0.0, 0.0));
+    ///
+    /// assert!(ether.is_reachable("a", "b"));
+    /// assert!(ether.is_reachable("b", "c"));
+    /// assert!(!ether.is_reachable("a", "c"));
     /// ```
-    pub fn get_driver(&self, name: &str) -> Option<WirelessModemFake> {
-        let devices = self.devices.lock().expect("Fail to get lock on devices");
+    pub fn set_propagation_model(&mut self, model: PropagationModel) {
+        self.lock_state().propagation_model = model;
+    }
 
-        for device in devices.iter() {
-            if device.get_name() == name {
-                return Some(WirelessModemFake::clone(&device));
-            }
+    /// Gets the propagation model of the ether.
+    pub fn get_propagation_model(&self) -> PropagationModel {
+        self.lock_state().propagation_model
+    }
+
+    /// Places the device at given position within the ether.
+    /// Devices without position are not affected by the propagation model.
+    pub fn set_position(&mut self, device: &str, position: Position) {
+        self.lock_state()
+            .nodes
+            .entry(device.to_owned())
+            .or_default()
+            .set_position(position);
+    }
+
+    /// Gets current position of the device within the ether.
+    pub fn get_position(&self, device: &str) -> Option<Position> {
+        self.lock_state().nodes.get(device).map(Node::get_position)
+    }
+
+    /// Removes position and mobility model of the device, so the device
+    /// is not affected by the propagation model anymore.
+    pub fn remove_position(&mut self, device: &str) {
+        self.lock_state().nodes.remove(device);
+    }
+
+    /// Sets the model, which moves the device as the simulation runs.
+    /// The position is updated once per tick, before the bytes are transferred.
+    /// Device, which has no position yet, starts at the origin.
+    /// Panics if the model is invalid, see `MobilityModel`.
+    /// ```
+    /// use proto_lab::{EtherSimulator, MobilityModel, Position};
+    ///
+    /// let mut ether = EtherSimulator::new("my_ether");
+    /// ether.set_position("modem", Position::new(0.0, 0.0, 0.0));
+    /// ether.set_mobility_model(
+    ///     "modem",
+    ///     MobilityModel::Waypoints {
+    ///         waypoints: vec![Position::new(10.0, 0.0, 0.0)],
+    ///         speed: 4.0,
+    ///     },
+    /// );
+    ///
+    /// ether.start_tick();
+    /// ether.simulate();
+    /// ether.end_tick();
+    ///
+    /// assert_eq!(ether.get_position("modem"), Some(Position::new(4.0, 0.0, 0.0)));
+    /// ```
+    pub fn set_mobility_model(&mut self, device: &str, model: MobilityModel) {
+        self.try_set_mobility_model(device, model)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Same as `set_mobility_model`, but returns error instead of panicking,
+    /// when the model is invalid. Invalid model is not set.
+    /// ```
+    /// use proto_lab::{EtherSimulator, MobilityModel, Position, SimulatorError};
+    ///
+    /// let mut ether = EtherSimulator::new("my_ether");
+    /// assert_eq!(
+    ///     ether.try_set_mobility_model(
+    ///         "modem",
+    ///         MobilityModel::Waypoints {
+    ///             waypoints: vec![Position::new(10.0, 0.0, 0.0)],
+    ///             speed: -4.0,
+    ///         },
+    ///     ),
+    ///     Err(SimulatorError::InvalidMobilityModel { device: String::from("modem") })
+    /// );
+    /// assert_eq!(ether.get_mobility_model("modem"), None);
+    /// ```
+    pub fn try_set_mobility_model(
+        &mut self,
+        device: &str,
+        model: MobilityModel,
+    ) -> Result<(), SimulatorError> {
+        if !model.is_valid() {
+            return Err(SimulatorError::InvalidMobilityModel {
+                device: device.to_owned(),
+            });
+        }
+        self.lock_state()
+            .nodes
+            .entry(device.to_owned())
+            .or_default()
+            .set_mobility_model(Some(model));
+        Ok(())
+    }
+
+    /// Stops the device at it's current position.
+    pub fn remove_mobility_model(&mut self, device: &str) {
+        if let Some(node) = self.lock_state().nodes.get_mut(device) {
+            node.set_mobility_model(None);
         }
-        None
     }
 
-    /// Gets the broadcasted byte from broadasting devices.
-    /// Simulates data collections within the ether.
+    /// Gets the mobility model of the device.
+    pub fn get_mobility_model(&self, device: &str) -> Option<MobilityModel> {
+        self.lock_state()
+            .nodes
+            .get(device)
+            .and_then(Node::get_mobility_model)
+            .cloned()
+    }
+
+    /// Sets the strength of the signal in dBm, with which `receiver` hears `sender`.
+    /// Is used by `CollisionPolicy::Capture`. Not configured links have the strength,
+    /// computed by `PropagationModel::LogDistance`, or 0 dBm otherwise.
     /// ```
     /// use proto_lab::EtherSimulator;
-    /// use proto_lab::IODriverSimulator;
-    /// use proto_lab::WirelessModemFake;
     ///
-    /// let mut ether = EtherSimulator::new("ether");
+    /// let mut ether = EtherSimulator::new("my_ether");
+    /// assert_eq!(ether.get_signal_strength("modem_1", "modem_2"), 0.0);
+    /// ether.set_signal_strength("modem_1", "modem_2", -40.0);
+    /// assert_eq!(ether.get_signal_strength("modem_1", "modem_2"), -40.0);
+    /// assert_eq!(ether.get_signal_strength("modem_2", "modem_1"), 0.0);
+    /// ```
+    pub fn set_signal_strength(&mut self, sender: &str, receiver: &str, strength: f32) {
+        self.lock_state()
+            .get_link_mut(sender, receiver)
+            .signal_strength = Some(strength);
+    }
+
+    /// Gets the strength of the signal in dBm, with which `receiver` hears `sender`.
+    pub fn get_signal_strength(&self, sender: &str, receiver: &str) -> f32 {
+        self.lock_state().get_signal_strength(sender, receiver)
+    }
+
+    /// Sets the probability (0.0 ..= 1.0) for the byte, broadcasted by `sender`,
+    /// to be lost on it's way to `receiver`. Lost byte is not heard at all,
+    /// so it does not collide with other broadcasts.
+    /// Losses are driven by the seeded generator of the ether, see `set_seed`.
+    /// ```
+    /// use proto_lab::{EtherSimulator, IODriverSimulator, WirelessModemFake};
+    ///
+    /// let mut ether = EtherSimulator::new("my_ether");
     ///
     /// let modem_1 = WirelessModemFake::new("modem_1");
     /// let modem_2 = WirelessModemFake::new("modem_2");
-    ///
     /// ether.register_driver(modem_1.clone());
     /// ether.register_driver(modem_2.clone());
     ///
-    /// modem_1.put_to_rx_pin(b'a');
-    /// modem_1.put_to_rx_pin(b'b');
+    /// ether.set_link_loss("modem_1", "modem_2", 1.0);
+    /// assert_eq!(ether.get_link_loss("modem_1", "modem_2"), 1.0);
     ///
+    /// modem_1.put_to_rx_pin(b'a');
     /// ether.start_tick();
     /// ether.simulate();
     /// ether.end_tick();
+    /// assert_eq!(modem_2.get_from_tx_pin(), None);
     ///
-    /// assert_eq!(modem_2.get_from_tx_pin().expect("No byte"), b'a');
-    ///
+    /// // Opposite direction of the link is not lossy.
+    /// modem_2.put_to_rx_pin(b'b');
     /// ether.start_tick();
     /// ether.simulate();
     /// ether.end_tick();
-    ///
-    /// assert_eq!(modem_2.get_from_tx_pin().expect("No byte"), b'b');
+    /// assert_eq!(modem_1.get_from_tx_pin(), Some(b'b'));
     /// ```
-    fn get_current_byte(&mut self) -> Option<u8> {
-        let devices = self.devices.lock().expect("Fail to get lock on devices");
-        let mut broadcasted_data: BTreeMap<String, u8> = BTreeMap::new();
-
-        // Collect all broadcasts.
-        for device in devices.iter() {
-            if let Some(byte) = device.get_from_device_network_side() {
-                broadcasted_data
-                    .entry(device.get_name().to_owned())
-                    .and_modify(|el| *el = byte)
-                    .or_insert(byte);
-            }
-        }
-
-        // Deterministic simulation of data collision
-        // In case when multiple devices are broadasting at the same time -
-        // clears out data of device which had broadcast on the previous
-        // iteration of simulation.
-        match self.last_broadcasted_device.take() {
-            None => (),
-            Some(name_of_last_broadcasted) => {
-                if broadcasted_data.len() > 1 {
-                    broadcasted_data.retain(|name, _| *name.clone() != name_of_last_broadcasted);
-                }
-            }
-        }
-
-        for (name, byte) in broadcasted_data.iter() {
-            self.last_broadcasted_device.replace(name.clone());
-            return Some(*byte);
-        }
-
-        self.last_broadcasted_device.take();
-        return None;
+    pub fn set_link_loss(&mut self, sender: &str, receiver: &str, probability: f64) {
+        self.lock_state()
+            .get_link_mut(sender, receiver)
+            .loss_probability = probability;
     }
 
-    /// Prepares all the registered devices for starting of simulation during tick.
-    pub fn start_tick(&self) {
-        let devices = self.devices.lock().expect("Fail to get lock on devices");
-        for device in devices.iter() {
-            device.start_tick();
-        }
+    /// Gets the probability for the byte, broadcasted by `sender`,
+    /// to be lost on it's way to `receiver`.
+    pub fn get_link_loss(&self, sender: &str, receiver: &str) -> f64 {
+        self.lock_state()
+            .get_link(sender, receiver)
+            .map_or(0.0, |link| link.loss_probability)
     }
 
-    /// Prepares all the registered devices for ending of simulation during tick.
-    pub fn end_tick(&self) {
-        let devices = self.devices.lock().expect("Fail to get lock on devices");
-        for device in devices.iter() {
-            device.end_tick();
-        }
+    /// Sets the count of ticks, the byte broadcasted by `sender` travels to `receiver`.
+    /// Delayed bytes collide with the bytes, which reach the receiver at the same tick.
+    /// Links are not delayed by default.
+    /// ```
+    /// use proto_lab::{EtherSimulator, IODriverSimulator, WirelessModemFake};
+    ///
+    /// let mut ether = EtherSimulator::new("my_ether");
+    ///
+    /// let modem_1 = WirelessModemFake::new("modem_1");
+    /// let modem_2 = WirelessModemFake::new("modem_2");
+    /// ether.register_driver(modem_1.clone());
+    /// ether.register_driver(modem_2.clone());
+    ///
+    /// ether.set_link_delay("modem_1", "modem_2", 2);
+    /// assert_eq!(ether.get_link_delay("modem_1", "modem_2"), 2);
+    ///
+    /// modem_1.put_to_rx_pin(b'a');
+    /// for _ in 0..2 {
+    ///     ether.start_tick();
+    ///     ether.simulate();
+    ///     ether.end_tick();
+    ///     assert_eq!(modem_2.get_from_tx_pin(), None);
+    /// }
+    ///
+    /// ether.start_tick();
+    /// ether.simulate();
+    /// ether.end_tick();
+    /// assert_eq!(modem_2.get_from_tx_pin(), Some(b'a'));
+    /// ```
+    pub fn set_link_delay(&mut self, sender: &str, receiver: &str, ticks: u64) {
+        self.lock_state().get_link_mut(sender, receiver).delay_ticks = ticks;
     }
 
-    /// This operation shall be called only during tick is active.
-    pub fn simulate(&mut self) {
-        let current_byte = self.get_current_byte();
+    /// Gets the count of ticks, the byte broadcasted by `sender` travels to `receiver`.
+    pub fn get_link_delay(&self, sender: &str, receiver: &str) -> u64 {
+        self.lock_state().get_link_delay(sender, receiver)
+    }
 
-        let devices = self.devices.lock().expect("Fail to get lock on devices");
+    /// Sets the probability (0.0 ..= 1.0) for each bit of the heard bytes to be flipped.
+    /// Applies to all the links of the ether, which do not override it with
+    /// `set_link_bit_error_rate`. Bit errors are driven by the seeded generator
+    /// of the ether, see `set_seed`.
+    /// ```
+    /// use proto_lab::{EtherSimulator, IODriverSimulator, WirelessModemFake};
+    ///
+    /// let mut ether = EtherSimulator::new("my_ether");
+    ///
+    /// let modem_1 = WirelessModemFake::new("modem_1");
+    /// let modem_2 = WirelessModemFake::new("modem_2");
+    /// ether.register_driver(modem_1.clone());
+    /// ether.register_driver(modem_2.clone());
+    ///
+    /// ether.set_bit_error_rate(1.0);
+    /// assert_eq!(ether.get_bit_error_rate(), 1.0);
+    ///
+    /// modem_1.put_to_rx_pin(0b1111_0000);
+    /// ether.start_tick();
+    /// ether.simulate();
+    /// ether.end_tick();
+    /// assert_eq!(modem_2.get_from_tx_pin(), Some(0b0000_1111));
+    /// ```
+    pub fn set_bit_error_rate(&mut self, bit_error_rate: f64) {
+        self.lock_state().bit_error_rate = bit_error_rate;
+    }
 
-        if let Some(current_byte) = current_byte {
-            for device in devices.iter() {
-                device.put_to_device_network_side(current_byte);
-            }
-        }
+    /// Gets the bit error rate of the ether.
+    pub fn get_bit_error_rate(&self) -> f64 {
+        self.lock_state().bit_error_rate
     }
 
-    /// Clones itself.
-    /// Also makes all internal data shared to be able to use from multiple threads.
+    /// Overrides the bit error rate of the ether for the link from `sender` to `receiver`.
     /// ```
     /// use proto_lab::EtherSimulator;
-    /// use proto_lab::WirelessModemFake;
-    /// use proto_lab::IODriverSimulator;
     ///
     /// let mut ether = EtherSimulator::new("my_ether");
-    /// let ether_clone = ether.clone();
+    /// ether.set_bit_error_rate(0.01);
+    /// ether.set_link_bit_error_rate("modem_1", "modem_2", 0.5);
     ///
-    /// assert_eq!(ether.get_name(), ether_clone.get_name());
-    pub fn clone(&self) -> EtherSimulator {
-        EtherSimulator {
-            name: String::from(&self.name),
-            devices: Arc::clone(&self.devices),
-            last_broadcasted_device: self.last_broadcasted_device.clone(),
+    /// assert_eq!(ether.get_link_bit_error_rate("modem_1", "modem_2"), 0.5);
+    /// assert_eq!(ether.get_link_bit_error_rate("modem_2", "modem_1"), 0.01);
+    /// ```
+    pub fn set_link_bit_error_rate(&mut self, sender: &str, receiver: &str, bit_error_rate: f64) {
+        self.lock_state()
+            .get_link_mut(sender, receiver)
+            .bit_error_rate = Some(bit_error_rate);
+    }
+
+    /// Gets the bit error rate of the link from `sender` to `receiver`.
+    pub fn get_link_bit_error_rate(&self, sender: &str, receiver: &str) -> f64 {
+        self.lock_state().get_bit_error_rate(sender, receiver)
+    }
+
+    /// Sets two-state channel model to the link from `sender` to `receiver`,
+    /// so the bytes are lost in bursts rather than independently.
+    /// The link starts in good state, and it's state is stepped once per tick
+    /// by the seeded generator of the ether, see `set_seed`.
+    /// ```
+    /// use proto_lab::{EtherSimulator, GilbertElliottModel, IODriverSimulator, WirelessModemFake};
+    ///
+    /// let mut ether = EtherSimulator::new("my_ether");
+    ///
+    /// let modem_1 = WirelessModemFake::new("modem_1");
+    /// let modem_2 = WirelessModemFake::new("modem_2");
+    /// ether.register_driver(modem_1.clone());
+    /// ether.register_driver(modem_2.clone());
+    ///
+    /// let model = GilbertElliottModel {
+    ///     good_to_bad: 1.0,
+    ///     bad_to_good: 0.0,
+    ///     loss_in_good: 0.0,
+    ///     loss_in_bad: 1.0,
+    /// };
+    /// ether.set_link_channel_model("modem_1", "modem_2", model);
+    /// assert_eq!(ether.get_link_channel_model("modem_1", "modem_2"), Some(model));
+    /// assert!(!ether.is_link_in_bad_state("modem_1", "modem_2"));
+    ///
+    /// modem_1.put_to_rx_pin(b'a');
+    /// ether.start_tick();
+    /// ether.simulate();
+    /// ether.end_tick();
+    ///
+    /// assert!(ether.is_link_in_bad_state("modem_1", "modem_2"));
+    /// assert_eq!(modem_2.get_from_tx_pin(), None);
+    /// ```
+    pub fn set_link_channel_model(
+        &mut self,
+        sender: &str,
+        receiver: &str,
+        model: GilbertElliottModel,
+    ) {
+        self.lock_state().get_link_mut(sender, receiver).channel =
+            Some(GilbertElliottChannel::new(model));
+    }
+
+    /// Removes channel model from the link from `sender` to `receiver`.
+    pub fn remove_link_channel_model(&mut self, sender: &str, receiver: &str) {
+        self.lock_state().get_link_mut(sender, receiver).channel = None;
+    }
+
+    /// Gets channel model of the link from `sender` to `receiver`.
+    pub fn get_link_channel_model(
+        &self,
+        sender: &str,
+        receiver: &str,
+    ) -> Option<GilbertElliottModel> {
+        self.lock_state()
+            .get_link(sender, receiver)
+            .and_then(|link| link.channel.as_ref())
+            .map(GilbertElliottChannel::get_model)
+    }
+
+    /// Checks whether the channel model of the link from `sender` to `receiver`
+    /// is currently in bad state.
+    pub fn is_link_in_bad_state(&self, sender: &str, receiver: &str) -> bool {
+        self.lock_state()
+            .get_link(sender, receiver)
+            .and_then(|link| link.channel.as_ref())
+            .is_some_and(GilbertElliottChannel::is_bad)
+    }
+
+    /// Registers a new device (driver / modem).
+    /// Any type implementing `IODriverSimulator` can be registered, so custom
+    /// device models can share the same ether with the stock modems.
+    /// ```
+    /// use proto_lab::EtherSimulator;
+    /// use proto_lab::WirelessModemFake;
+    /// use proto_lab::IODriverSimulator;
+    ///
+    /// let mut ether = EtherSimulator::new("my_ether");
+    /// ether.register_driver(WirelessModemFake::new("my_modem"));
+    /// assert_eq!(ether.get_driver("my_modem").unwrap().get_name(), "my_modem");
+    /// ```
+    pub fn register_driver<D>(&mut self, driver: D)
+    where
+        D: IODriverSimulator + Send + Sync + 'static,
+    {
+        self.register_driver_handle(Arc::new(driver));
+    }
+
+    /// Registers a device, which is already wrapped into shared handle.
+    /// ```
+    /// use std::sync::Arc;
+    /// use proto_lab::{DriverHandle, EtherSimulator, IODriverSimulator, WirelessModemFake};
+    ///
+    /// let handle: DriverHandle = Arc::new(WirelessModemFake::new("my_modem"));
+    ///
+    /// let mut ether = EtherSimulator::new("my_ether");
+    /// ether.register_driver_handle(Arc::clone(&handle));
+    /// assert_eq!(ether.get_driver("my_modem").unwrap().get_name(), "my_modem");
+    /// ```
+    pub fn register_driver_handle(&mut self, driver: DriverHandle) {
+        let mut devices = self.devices.lock().expect("Fail to get lock on devices");
+        devices.push(driver);
+    }
+
+    /// Unregisters a device. Configuration of it's links, it's position and
+    /// it's bytes, which are still on the way, are removed as well.
+    /// ```
+    /// use proto_lab::EtherSimulator;
+    /// use proto_lab::WirelessModemFake;
+    /// use proto_lab::IODriverSimulator;
+    ///
+    /// let mut ether = EtherSimulator::new("my_ether");
+    /// ether.register_driver(WirelessModemFake::new("my_modem"));
+    /// ether.unregister_driver("my_modem");
+    /// assert!(ether.get_driver("my_modem").is_none());
+    /// ```
+    pub fn unregister_driver(&mut self, name: &str) {
+        let mut devices = self.devices.lock().expect("Fail to get lock on devices");
+
+        loop {
+            let mut index_to_remove: Option<_> = None;
+
+            for (i, device) in devices.iter_mut().enumerate() {
+                if device.get_name() == name {
+                    index_to_remove.replace(i);
+                }
+            }
+
+            match index_to_remove {
+                Some(i) => devices.remove(i),
+                None => break,
+            };
+        }
+
+        self.lock_state().remove_device(name);
+    }
+
+    /// Gets a registered device
+    /// ```
+    /// use proto_lab::EtherSimulator;
+    /// use proto_lab::WirelessModemFake;
+    /// use proto_lab::IODriverSimulator;
+    ///
+    /// let mut ether = EtherSimulator::new("my_ether");
+    /// assert!(ether.get_driver("my_modem").is_none());
+    /// ether.register_driver(WirelessModemFake::new("my_modem"));
+    /// assert_eq!(ether.get_driver("my_modem").unwrap().get_name(), "my_modem");
+    /// ```
+    pub fn get_driver(&self, name: &str) -> Option<DriverHandle> {
+        let devices = self.devices.lock().expect("Fail to get lock on devices");
+
+        for device in devices.iter() {
+            if device.get_name() == name {
+                return Some(Arc::clone(device));
+            }
+        }
+        None
+    }
+
+    /// Collects the bytes, broadcasted by the devices at current tick.
+    fn collect_broadcasts(devices: &[DriverHandle]) -> BTreeMap<String, Vec<u8>> {
+        let mut broadcasted_data: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+
+        for device in devices.iter() {
+            let bytes = device.get_bytes_from_device_network_side();
+            // Device, which is in the middle of transmission, is on air without bytes.
+            if !bytes.is_empty() || device.is_on_air() {
+                broadcasted_data.insert(device.get_name().to_owned(), bytes);
+            }
+        }
+
+        broadcasted_data
+    }
+
+    /// Prepares all the registered devices for starting of simulation during tick.
+    pub fn start_tick(&self) {
+        let devices = self
+            .devices
+            .lock()
+            .expect("Fail to get lock on devices")
+            .clone();
+        for device in devices.iter() {
+            device.start_tick();
+        }
+        self.tick_devices
+            .lock()
+            .expect("Fail to get lock on tick devices")
+            .replace(devices);
+    }
+
+    /// Prepares all the registered devices for ending of simulation during tick.
+    pub fn end_tick(&self) {
+        let devices = self
+            .tick_devices
+            .lock()
+            .expect("Fail to get lock on tick devices")
+            .take();
+        let devices = devices.unwrap_or_else(|| {
+            self.devices
+                .lock()
+                .expect("Fail to get lock on devices")
+                .clone()
+        });
+        for device in devices.iter() {
+            device.end_tick();
+        }
+    }
+
+    /// Returns devices, which are taking part in the current tick.
+    fn get_tick_devices(&self) -> Vec<DriverHandle> {
+        let tick_devices = self
+            .tick_devices
+            .lock()
+            .expect("Fail to get lock on tick devices");
+        match tick_devices.as_ref() {
+            Some(devices) => devices.clone(),
+            None => self
+                .devices
+                .lock()
+                .expect("Fail to get lock on devices")
+                .clone(),
+        }
+    }
+
+    /// Delivers broadcasted bytes to the devices of the ether.
+    /// Each device hears broadcasts of all the other devices, except the lost ones,
+    /// with bits flipped according to the bit error rate of the link, and data
+    /// collisions are resolved for each listening device separately
+    /// according to the `CollisionPolicy` of the ether.
+    /// This operation shall be called only during tick is active.
+    /// ```
+    /// use proto_lab::EtherSimulator;
+    /// use proto_lab::IODriverSimulator;
+    /// use proto_lab::WirelessModemFake;
+    ///
+    /// let mut ether = EtherSimulator::new("ether");
+    ///
+    /// let modem_1 = WirelessModemFake::new("modem_1");
+    /// let modem_2 = WirelessModemFake::new("modem_2");
+    ///
+    /// ether.register_driver(modem_1.clone());
+    /// ether.register_driver(modem_2.clone());
+    ///
+    /// modem_1.put_to_rx_pin(b'a');
+    /// modem_1.put_to_rx_pin(b'b');
+    ///
+    /// ether.start_tick();
+    /// ether.simulate();
+    /// ether.end_tick();
+    ///
+    /// assert_eq!(modem_2.get_from_tx_pin().expect("No byte"), b'a');
+    ///
+    /// ether.start_tick();
+    /// ether.simulate();
+    /// ether.end_tick();
+    ///
+    /// assert_eq!(modem_2.get_from_tx_pin().expect("No byte"), b'b');
+    /// ```
+    pub fn simulate(&mut self) {
+        let devices = self.get_tick_devices();
+        let broadcasted_data = Self::collect_broadcasts(&devices);
+        let data_rates: BTreeMap<&str, Option<DataRate>> = devices
+            .iter()
+            .map(|device| (device.get_name(), device.data_rate()))
+            .collect();
+
+        let mut state = self.lock_state();
+        let tick = state.tick;
+        state.tick += 1;
+        let arrivals = state.in_flight.remove(&tick).unwrap_or_default();
+
+        state.step_nodes();
+        state.step_channels();
+
+        let senders: BTreeSet<&str> = broadcasted_data
+            .keys()
+            .map(String::as_str)
+            .chain(arrivals.iter().map(|delivery| delivery.sender.as_str()))
+            .collect();
+        state.draw_priorities(&senders);
+
+        for device in devices.iter() {
+            let receiver = device.get_name();
+            let arrived: Vec<&Delivery> = arrivals
+                .iter()
+                .filter(|delivery| delivery.receiver == receiver)
+                .collect();
+
+            // Arrived transmission is sensed, even when the bytes are not received.
+            let mut sensed: Vec<f32> = arrived
+                .iter()
+                .map(|delivery| state.get_signal_strength(&delivery.sender, receiver))
+                .collect();
+
+            let mut heard: Vec<(&str, Vec<u8>)> = arrived
+                .iter()
+                .filter_map(|delivery| {
+                    let bytes = delivery.bytes.clone()?;
+                    Some((delivery.sender.as_str(), bytes))
+                })
+                .collect();
+
+            for (sender, bytes) in broadcasted_data.iter() {
+                if sender.as_str() == receiver || !state.is_reachable(sender, receiver) {
+                    continue;
+                }
+                let state = &mut *state;
+                let received = state
+                    .rate_mismatch_policy
+                    .transfer(
+                        data_rates[sender.as_str()],
+                        device.data_rate(),
+                        bytes.clone(),
+                        &mut state.rng,
+                    )
+                    .and_then(|bytes| {
+                        let mut received = vec![];
+                        for byte in bytes.iter() {
+                            if !state.is_lost(sender, receiver) {
+                                received.push(state.apply_bit_errors(sender, receiver, *byte));
+                            }
+                        }
+                        // Transmission without completed bytes still takes part in collisions.
+                        (!received.is_empty() || bytes.is_empty()).then_some(received)
+                    });
+                match state.get_link_delay(sender, receiver) {
+                    0 => {
+                        // Transmission is sensed, even when the bytes are not received.
+                        sensed.push(state.get_signal_strength(sender, receiver));
+                        if let Some(received) = received {
+                            heard.push((sender.as_str(), received));
+                        }
+                    }
+                    delay => state
+                        .in_flight
+                        .entry(tick + delay)
+                        .or_default()
+                        .push(Delivery {
+                            sender: sender.clone(),
+                            receiver: receiver.to_owned(),
+                            bytes: received,
+                        }),
+                }
+            }
+            heard.sort_by_key(|(sender, _)| *sender);
+
+            for rssi in sensed {
+                device.put_rssi_to_device_network_side(rssi);
+            }
+
+            if let Some(bytes) = state.resolve_collision(receiver, &heard) {
+                device.put_bytes_to_device_network_side(&bytes);
+            }
+        }
+    }
+}
+
+impl Clone for EtherSimulator {
+    /// Clones itself.
+    /// Also makes all internal data shared to be able to use from multiple threads.
+    /// ```
+    /// use proto_lab::EtherSimulator;
+    /// use proto_lab::WirelessModemFake;
+    /// use proto_lab::IODriverSimulator;
+    ///
+    /// let mut ether = EtherSimulator::new("my_ether");
+    /// let ether_clone = ether.clone();
+    ///
+    /// assert_eq!(ether.get_name(), ether_clone.get_name());
+    /// ```
+    fn clone(&self) -> EtherSimulator {
+        EtherSimulator {
+            name: String::from(&self.name),
+            devices: Arc::clone(&self.devices),
+            tick_devices: Mutex::new(None),
+            state: Arc::clone(&self.state),
         }
     }
 }
@@ -215,10 +1139,11 @@ impl EtherSimulator {
 #[cfg(test)]
 mod test {
     #[test]
+    #[allow(clippy::useless_vec)]
     fn test_of_collisions() {
         use super::EtherSimulator;
         use super::IODriverSimulator;
-        use super::WirelessModemFake;
+        use crate::WirelessModemFake;
 
         let mut ether = EtherSimulator::new("ether");
 
@@ -255,4 +1180,873 @@ mod test {
 
         assert_eq!(received_vec, expected_vec);
     }
+
+    #[test]
+    fn test_of_custom_device_sharing_ether() {
+        use super::EtherSimulator;
+        use super::IODriverSimulator;
+        use crate::WirelessModemFake;
+        use std::sync::{Arc, Mutex};
+
+        // Device which only listens the ether and records everything it hears.
+        struct Sniffer {
+            heard: Arc<Mutex<Vec<u8>>>,
+        }
+
+        impl IODriverSimulator for Sniffer {
+            fn get_from_device_network_side(&self) -> Option<u8> {
+                None
+            }
+            fn put_to_device_network_side(&self, byte: u8) {
+                self.heard.lock().unwrap().push(byte);
+            }
+            fn get_from_tx_pin(&self) -> Option<u8> {
+                None
+            }
+            fn put_to_rx_pin(&self, _byte: u8) {}
+            fn start_tick(&self) {}
+            fn end_tick(&self) {}
+            fn readable(&self) -> bool {
+                false
+            }
+            fn writable(&self) -> bool {
+                false
+            }
+            fn get_name(&self) -> &str {
+                "sniffer"
+            }
+        }
+
+        let heard = Arc::new(Mutex::new(vec![]));
+
+        let mut ether = EtherSimulator::new("ether");
+        let modem = WirelessModemFake::new("modem");
+
+        ether.register_driver(modem.clone());
+        ether.register_driver(Sniffer {
+            heard: Arc::clone(&heard),
+        });
+
+        assert_eq!(ether.get_driver("sniffer").unwrap().get_name(), "sniffer");
+
+        modem.put_to_rx_pin(b'a');
+        modem.put_to_rx_pin(b'b');
+
+        for _ in 0..3 {
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+        }
+
+        assert_eq!(*heard.lock().unwrap(), vec![b'a', b'b']);
+
+        ether.unregister_driver("sniffer");
+        assert!(ether.get_driver("sniffer").is_none());
+    }
+
+    #[test]
+    fn test_unregistered_device_leaves_no_state() {
+        use super::EtherSimulator;
+        use crate::{IODriverSimulator, MobilityModel, Position, WirelessModemFake};
+
+        let mut ether = EtherSimulator::new("ether");
+
+        let old_modem = WirelessModemFake::new("modem");
+        let receiving_modem = WirelessModemFake::new("receiver");
+
+        ether.register_driver(old_modem.clone());
+        ether.register_driver(receiving_modem.clone());
+
+        ether.set_link_delay("modem", "receiver", 2);
+        ether.set_link_loss("receiver", "modem", 1.0);
+        ether.set_reachable("modem", "receiver", false);
+        ether.set_reachable("modem", "receiver", true);
+        ether.set_signal_strength("modem", "receiver", -90.0);
+        ether.set_position("modem", Position::new(1.0, 0.0, 0.0));
+        ether.set_mobility_model(
+            "modem",
+            MobilityModel::Waypoints {
+                waypoints: vec![Position::new(100.0, 0.0, 0.0)],
+                speed: 1.0,
+            },
+        );
+
+        // Byte of the old modem is on the way, when the modem is unregistered.
+        old_modem.put_to_rx_pin(b'o');
+        ether.start_tick();
+        ether.simulate();
+        ether.end_tick();
+        ether.unregister_driver("modem");
+
+        assert_eq!(ether.get_link_delay("modem", "receiver"), 0);
+        assert_eq!(ether.get_link_loss("receiver", "modem"), 0.0);
+        assert_eq!(ether.get_signal_strength("modem", "receiver"), 0.0);
+        assert_eq!(ether.get_position("modem"), None);
+        assert_eq!(ether.get_mobility_model("modem"), None);
+
+        let new_modem = WirelessModemFake::new("modem");
+        ether.register_driver(new_modem.clone());
+
+        new_modem.put_to_rx_pin(b'n');
+        let mut received = vec![];
+        for _ in 0..3 {
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+            received.extend(receiving_modem.get_from_tx_pin());
+        }
+        assert_eq!(received, vec![b'n']);
+
+        receiving_modem.put_to_rx_pin(b'r');
+        ether.start_tick();
+        ether.simulate();
+        ether.end_tick();
+        assert_eq!(new_modem.get_from_tx_pin(), Some(b'r'));
+    }
+
+    #[test]
+    fn test_registration_in_the_middle_of_tick() {
+        use super::EtherSimulator;
+        use super::IODriverSimulator;
+        use crate::WirelessModemFake;
+
+        let mut ether = EtherSimulator::new("ether");
+        let modem_1 = WirelessModemFake::new("modem_1");
+        let modem_2 = WirelessModemFake::new("modem_2");
+
+        ether.register_driver(modem_1.clone());
+        modem_1.put_to_rx_pin(b'a');
+        modem_1.put_to_rx_pin(b'b');
+
+        ether.start_tick();
+        ether.register_driver(modem_2.clone());
+        ether.simulate();
+        ether.end_tick();
+
+        assert_eq!(modem_2.get_from_tx_pin(), None);
+
+        ether.start_tick();
+        ether.simulate();
+        ether.end_tick();
+
+        assert_eq!(modem_2.get_from_tx_pin(), Some(b'b'));
+    }
+
+    fn run_random_contention(seed: u64) -> Vec<u8> {
+        use super::EtherSimulator;
+        use crate::{CollisionPolicy, WirelessModemFake};
+
+        let mut ether = EtherSimulator::new("ether");
+        ether.set_collision_policy(CollisionPolicy::Random);
+        ether.set_seed(seed);
+
+        let sending_modem_1 = WirelessModemFake::new("modem_1");
+        let sending_modem_2 = WirelessModemFake::new("modem_2");
+        let receiving_modem = WirelessModemFake::new("modem_3");
+
+        ether.register_driver(sending_modem_1.clone());
+        ether.register_driver(sending_modem_2.clone());
+        ether.register_driver(receiving_modem.clone());
+
+        sending_modem_1.write(&[b'1'; 32]).unwrap();
+        sending_modem_2.write(&[b'2'; 32]).unwrap();
+
+        for _ in 0..32 {
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+        }
+
+        let mut received = [0u8; 32];
+        assert_eq!(receiving_modem.read(&mut received), Ok(32));
+        received.to_vec()
+    }
+
+    #[test]
+    fn test_of_seeded_random_collisions() {
+        let outcome_1 = run_random_contention(1);
+
+        assert_eq!(outcome_1, run_random_contention(1));
+        assert_ne!(outcome_1, run_random_contention(2));
+
+        assert!(outcome_1.contains(&b'1'));
+        assert!(outcome_1.contains(&b'2'));
+    }
+
+    #[test]
+    fn test_of_destructive_collisions() {
+        use super::EtherSimulator;
+        use crate::{CollisionCorruption, CollisionPolicy, IODriverSimulator, WirelessModemFake};
+
+        for (corruption, expected) in [
+            (CollisionCorruption::Silence, None),
+            (CollisionCorruption::BitwiseOr, Some(0b0111)),
+            (CollisionCorruption::BitwiseXor, Some(0b0110)),
+        ] {
+            let mut ether = EtherSimulator::new("ether");
+            ether.set_collision_policy(CollisionPolicy::Destructive(corruption));
+
+            let sending_modem_1 = WirelessModemFake::new("modem_1");
+            let sending_modem_2 = WirelessModemFake::new("modem_2");
+            let receiving_modem = WirelessModemFake::new("modem_3");
+
+            ether.register_driver(sending_modem_1.clone());
+            ether.register_driver(sending_modem_2.clone());
+            ether.register_driver(receiving_modem.clone());
+
+            sending_modem_1.put_to_rx_pin(0b0011);
+            sending_modem_2.put_to_rx_pin(0b0101);
+            sending_modem_2.put_to_rx_pin(0b1000);
+
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+
+            assert_eq!(receiving_modem.get_from_tx_pin(), expected);
+
+            // Single broadcaster is heard cleanly.
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+
+            assert_eq!(receiving_modem.get_from_tx_pin(), Some(0b1000));
+        }
+    }
+
+    #[test]
+    fn test_of_capture_effect() {
+        use super::EtherSimulator;
+        use crate::{CollisionCorruption, CollisionPolicy, IODriverSimulator, WirelessModemFake};
+
+        let mut ether = EtherSimulator::new("ether");
+        ether.set_collision_policy(CollisionPolicy::Capture {
+            margin: 10.0,
+            corruption: CollisionCorruption::Silence,
+        });
+
+        let sending_modem_1 = WirelessModemFake::new("modem_1");
+        let sending_modem_2 = WirelessModemFake::new("modem_2");
+        let near_modem = WirelessModemFake::new("near");
+        let between_modem = WirelessModemFake::new("between");
+
+        ether.register_driver(sending_modem_1.clone());
+        ether.register_driver(sending_modem_2.clone());
+        ether.register_driver(near_modem.clone());
+        ether.register_driver(between_modem.clone());
+
+        // Near modem hears modem_2 much louder, than modem_1.
+        ether.set_signal_strength("modem_1", "near", -70.0);
+        ether.set_signal_strength("modem_2", "near", -30.0);
+
+        // Between modem hears both of them nearly the same.
+        ether.set_signal_strength("modem_1", "between", -50.0);
+        ether.set_signal_strength("modem_2", "between", -45.0);
+
+        sending_modem_1.put_to_rx_pin(b'1');
+        sending_modem_2.put_to_rx_pin(b'2');
+
+        ether.start_tick();
+        ether.simulate();
+        ether.end_tick();
+
+        assert_eq!(near_modem.get_from_tx_pin(), Some(b'2'));
+        assert_eq!(between_modem.get_from_tx_pin(), None);
+    }
+
+    /// Sends a byte over the "sender" -> "receiver" link each tick,
+    /// and tells for each tick whether the byte was delivered.
+    fn run_link<F>(seed: u64, ticks: usize, configure_link: F) -> Vec<bool>
+    where
+        F: FnOnce(&mut super::EtherSimulator),
+    {
+        use super::EtherSimulator;
+        use crate::{IODriverSimulator, WirelessModemFake};
+
+        let mut ether = EtherSimulator::new("ether");
+        ether.set_seed(seed);
+
+        let sending_modem = WirelessModemFake::new("sender");
+        let receiving_modem = WirelessModemFake::new("receiver");
+
+        ether.register_driver(sending_modem.clone());
+        ether.register_driver(receiving_modem.clone());
+        configure_link(&mut ether);
+
+        let mut delivered = vec![];
+        for _ in 0..ticks {
+            sending_modem.put_to_rx_pin(b'a');
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+            delivered.push(receiving_modem.get_from_tx_pin().is_some());
+        }
+        delivered
+    }
+
+    /// Returns the fraction of lost bytes and the mean length of loss bursts.
+    fn loss_statistics(delivered: &[bool]) -> (f64, f64) {
+        let lost = delivered.iter().filter(|delivered| !**delivered).count();
+        let bursts = delivered
+            .windows(2)
+            .filter(|pair| pair[0] && !pair[1])
+            .count()
+            + delivered.first().is_some_and(|delivered| !delivered) as usize;
+        (
+            lost as f64 / delivered.len() as f64,
+            lost as f64 / bursts.max(1) as f64,
+        )
+    }
+
+    #[test]
+    fn test_of_seeded_link_loss() {
+        let run = |seed| {
+            run_link(seed, 10_000, |ether| {
+                ether.set_link_loss("sender", "receiver", 0.3)
+            })
+        };
+        let delivered = run(7);
+
+        assert_eq!(delivered, run(7));
+        assert_ne!(delivered, run(8));
+
+        let (loss_fraction, mean_burst_length) = loss_statistics(&delivered);
+        assert!((loss_fraction - 0.3).abs() < 0.03);
+        // Losses are independent, so bursts are short: 1 / (1 - 0.3) on average.
+        assert!((mean_burst_length - 1.0 / 0.7).abs() < 0.2);
+    }
+
+    #[test]
+    fn test_of_bursty_link_loss() {
+        use crate::GilbertElliottModel;
+
+        let run = |seed| {
+            run_link(seed, 10_000, |ether| {
+                ether.set_link_channel_model(
+                    "sender",
+                    "receiver",
+                    GilbertElliottModel {
+                        good_to_bad: 0.05,
+                        bad_to_good: 0.2,
+                        loss_in_good: 0.0,
+                        loss_in_bad: 1.0,
+                    },
+                )
+            })
+        };
+        let delivered = run(3);
+
+        assert_eq!(delivered, run(3));
+        assert_ne!(delivered, run(4));
+
+        let (loss_fraction, mean_burst_length) = loss_statistics(&delivered);
+        // Link spends 0.05 / (0.05 + 0.2) of time in the bad state.
+        assert!((loss_fraction - 0.2).abs() < 0.05);
+        // Bad state lasts 1 / 0.2 ticks on average, so losses come in bursts.
+        assert!((mean_burst_length - 5.0).abs() < 1.5);
+    }
+
+    #[test]
+    fn test_of_hidden_terminals() {
+        use super::EtherSimulator;
+        use crate::{CollisionCorruption, CollisionPolicy, IODriverSimulator, WirelessModemFake};
+
+        let mut ether = EtherSimulator::new("ether");
+        ether.set_collision_policy(CollisionPolicy::Destructive(CollisionCorruption::Silence));
+
+        let modem_a = WirelessModemFake::new("a");
+        let modem_b = WirelessModemFake::new("b");
+        let modem_c = WirelessModemFake::new("c");
+
+        ether.register_driver(modem_a.clone());
+        ether.register_driver(modem_b.clone());
+        ether.register_driver(modem_c.clone());
+
+        ether.set_reachable("a", "c", false);
+        ether.set_reachable("c", "a", false);
+
+        // `a` and `c` can not sense each other, so their broadcasts collide at `b`.
+        modem_a.put_to_rx_pin(b'a');
+        modem_c.put_to_rx_pin(b'c');
+
+        ether.start_tick();
+        ether.simulate();
+        ether.end_tick();
+
+        assert_eq!(modem_b.get_from_tx_pin(), None);
+        assert_eq!(modem_a.get_from_tx_pin(), None);
+        assert_eq!(modem_c.get_from_tx_pin(), None);
+
+        // One way link: `b` hears `a`, but `a` does not hear `b`.
+        ether.set_reachable("b", "a", false);
+        modem_b.put_to_rx_pin(b'b');
+
+        ether.start_tick();
+        ether.simulate();
+        ether.end_tick();
+
+        assert_eq!(modem_a.get_from_tx_pin(), None);
+        assert_eq!(modem_c.get_from_tx_pin(), Some(b'b'));
+    }
+
+    #[test]
+    fn test_of_moving_out_of_range() {
+        use super::EtherSimulator;
+        use crate::{
+            IODriverSimulator, MobilityModel, Position, PropagationModel, WirelessModemFake,
+        };
+
+        let mut ether = EtherSimulator::new("ether");
+        ether.set_propagation_model(PropagationModel::RadioRange(10.0));
+
+        let static_modem = WirelessModemFake::new("static");
+        let moving_modem = WirelessModemFake::new("moving");
+
+        ether.register_driver(static_modem.clone());
+        ether.register_driver(moving_modem.clone());
+
+        ether.set_position("static", Position::new(0.0, 0.0, 0.0));
+        ether.set_mobility_model(
+            "moving",
+            MobilityModel::ScriptedPath(vec![
+                (0, Position::new(5.0, 0.0, 0.0)),
+                (10, Position::new(25.0, 0.0, 0.0)),
+            ]),
+        );
+
+        let mut heard = vec![];
+        for _ in 0..10 {
+            static_modem.put_to_rx_pin(b'a');
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+            heard.push(moving_modem.get_from_tx_pin().is_some());
+        }
+
+        // Moving modem leaves the range at the 3rd tick.
+        assert_eq!(
+            heard,
+            [true, true, false, false, false, false, false, false, false, false]
+        );
+        assert_eq!(
+            ether.get_position("moving"),
+            Some(Position::new(25.0, 0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_of_path_loss_capture() {
+        use super::EtherSimulator;
+        use crate::{
+            CollisionCorruption, CollisionPolicy, IODriverSimulator, Position, PropagationModel,
+            WirelessModemFake,
+        };
+
+        let mut ether = EtherSimulator::new("ether");
+        ether.set_propagation_model(PropagationModel::LogDistance {
+            tx_power: 14.0,
+            reference_loss: 40.0,
+            exponent: 3.0,
+            sensitivity: -100.0,
+        });
+        ether.set_collision_policy(CollisionPolicy::Capture {
+            margin: 6.0,
+            corruption: CollisionCorruption::Silence,
+        });
+
+        let near_modem = WirelessModemFake::new("near");
+        let far_modem = WirelessModemFake::new("far");
+        let receiving_modem = WirelessModemFake::new("receiver");
+
+        ether.register_driver(near_modem.clone());
+        ether.register_driver(far_modem.clone());
+        ether.register_driver(receiving_modem.clone());
+
+        ether.set_position("receiver", Position::new(0.0, 0.0, 0.0));
+        ether.set_position("near", Position::new(10.0, 0.0, 0.0));
+        ether.set_position("far", Position::new(0.0, 100.0, 0.0));
+
+        assert_eq!(ether.get_signal_strength("near", "receiver"), -56.0);
+        assert_eq!(ether.get_signal_strength("far", "receiver"), -86.0);
+
+        near_modem.put_to_rx_pin(b'n');
+        far_modem.put_to_rx_pin(b'f');
+
+        ether.start_tick();
+        ether.simulate();
+        ether.end_tick();
+
+        assert_eq!(receiving_modem.get_from_tx_pin(), Some(b'n'));
+    }
+
+    #[test]
+    fn test_of_delayed_bytes_collision() {
+        use super::EtherSimulator;
+        use crate::{CollisionCorruption, CollisionPolicy, IODriverSimulator, WirelessModemFake};
+
+        let mut ether = EtherSimulator::new("ether");
+        ether.set_collision_policy(CollisionPolicy::Destructive(CollisionCorruption::BitwiseOr));
+
+        let far_modem = WirelessModemFake::new("far");
+        let near_modem = WirelessModemFake::new("near");
+        let receiving_modem = WirelessModemFake::new("receiver");
+
+        ether.register_driver(far_modem.clone());
+        ether.register_driver(near_modem.clone());
+        ether.register_driver(receiving_modem.clone());
+
+        ether.set_link_delay("far", "receiver", 1);
+
+        // Broadcasts of different ticks collide at the receiver.
+        far_modem.put_to_rx_pin(0b01);
+        ether.start_tick();
+        ether.simulate();
+        ether.end_tick();
+        assert_eq!(receiving_modem.get_from_tx_pin(), None);
+        assert_eq!(near_modem.get_from_tx_pin(), Some(0b01));
+
+        near_modem.put_to_rx_pin(0b10);
+        ether.start_tick();
+        ether.simulate();
+        ether.end_tick();
+        assert_eq!(receiving_modem.get_from_tx_pin(), Some(0b11));
+
+        // Broadcasts of the same tick do not.
+        far_modem.put_to_rx_pin(0b01);
+        near_modem.put_to_rx_pin(0b10);
+        let mut received = vec![];
+        for _ in 0..3 {
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+            received.extend(receiving_modem.get_from_tx_pin());
+        }
+        assert_eq!(received, vec![0b10, 0b01]);
+    }
+
+    #[test]
+    fn test_of_mixed_data_rates() {
+        use super::EtherSimulator;
+        use crate::{DataRate, WirelessModemFake};
+
+        let mut ether = EtherSimulator::new("ether");
+
+        let fast_modem = WirelessModemFake::new("fast");
+        let slow_modem = WirelessModemFake::new("slow");
+        let receiving_modem = WirelessModemFake::new("receiver");
+
+        fast_modem.set_data_rate(DataRate::BytesPerTick(4));
+        slow_modem.set_data_rate(DataRate::TicksPerByte(4));
+
+        ether.register_driver(fast_modem.clone());
+        ether.register_driver(slow_modem.clone());
+        ether.register_driver(receiving_modem.clone());
+
+        fast_modem.write(b"abcdefgh").unwrap();
+
+        let mut received = [0u8; 8];
+        for _ in 0..2 {
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+        }
+        assert_eq!(receiving_modem.read(&mut received), Ok(8));
+        assert_eq!(&received, b"abcdefgh");
+
+        slow_modem.write(b"ab").unwrap();
+
+        let mut ticks = 0;
+        while receiving_modem.read(&mut received[..1]) != Ok(1) {
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+            ticks += 1;
+        }
+        assert_eq!(ticks, 4);
+        assert_eq!(received[0], b'a');
+    }
+
+    #[test]
+    fn test_of_rate_mismatch_corruption() {
+        use super::EtherSimulator;
+        use crate::{DataRate, IODriverSimulator, RateMismatchPolicy, WirelessModemFake};
+
+        let mut ether = EtherSimulator::new("ether");
+        ether.set_rate_mismatch_policy(RateMismatchPolicy::Corrupt);
+
+        let sending_modem = WirelessModemFake::new("sender");
+        let matching_modem = WirelessModemFake::new("matching");
+        let mismatching_modem = WirelessModemFake::new("mismatching");
+
+        mismatching_modem.set_data_rate(DataRate::TicksPerByte(2));
+
+        ether.register_driver(sending_modem.clone());
+        ether.register_driver(matching_modem.clone());
+        ether.register_driver(mismatching_modem.clone());
+
+        sending_modem.write(b"hello").unwrap();
+        for _ in 0..5 {
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+        }
+
+        let mut received = [0u8; 5];
+        assert_eq!(matching_modem.read(&mut received), Ok(5));
+        assert_eq!(&received, b"hello");
+
+        assert_eq!(mismatching_modem.read(&mut received), Ok(5));
+        assert_ne!(&received, b"hello");
+
+        // Once configuration is negotiated - the data goes through.
+        mismatching_modem.set_data_rate(DataRate::BytesPerTick(1));
+        sending_modem.put_to_rx_pin(b'a');
+        ether.start_tick();
+        ether.simulate();
+        ether.end_tick();
+        assert_eq!(mismatching_modem.get_from_tx_pin(), Some(b'a'));
+    }
+
+    #[test]
+    fn test_of_carrier_sense() {
+        use super::EtherSimulator;
+        use crate::{IODriverSimulator, WirelessModemFake};
+
+        let mut ether = EtherSimulator::new("ether");
+
+        let modem_a = WirelessModemFake::new("a");
+        let modem_b = WirelessModemFake::new("b");
+        let modem_c = WirelessModemFake::new("c");
+
+        ether.register_driver(modem_a.clone());
+        ether.register_driver(modem_b.clone());
+        ether.register_driver(modem_c.clone());
+
+        ether.set_reachable("a", "c", false);
+        ether.set_signal_strength("a", "b", -70.0);
+        ether.set_signal_strength("c", "b", -50.0);
+        // Transmission is sensed even if the byte is lost.
+        ether.set_link_loss("c", "b", 1.0);
+
+        modem_a.put_to_rx_pin(b'a');
+        modem_c.put_to_rx_pin(b'c');
+
+        ether.start_tick();
+        ether.simulate();
+        ether.end_tick();
+
+        assert_eq!(modem_b.get_rssi(), Some(-50.0));
+        assert_eq!(modem_b.get_from_tx_pin(), Some(b'a'));
+        // Hidden terminal does not sense the transmission of `a`.
+        assert!(!modem_c.channel_busy());
+        assert!(modem_a.channel_busy());
+    }
+
+    #[test]
+    fn test_of_carrier_sense_over_delayed_lossy_link() {
+        use super::EtherSimulator;
+        use crate::{IODriverSimulator, WirelessModemFake};
+
+        let mut ether = EtherSimulator::new("ether");
+
+        let sending_modem = WirelessModemFake::new("sender");
+        let receiving_modem = WirelessModemFake::new("receiver");
+
+        ether.register_driver(sending_modem.clone());
+        ether.register_driver(receiving_modem.clone());
+        ether.set_link_delay("sender", "receiver", 2);
+        ether.set_link_loss("sender", "receiver", 1.0);
+
+        sending_modem.put_to_rx_pin(b'a');
+
+        let mut busy = vec![];
+        for _ in 0..4 {
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+            busy.push(receiving_modem.channel_busy());
+        }
+
+        // Lost transmission is sensed, when it arrives.
+        assert_eq!(busy, vec![false, false, true, false]);
+        assert_eq!(receiving_modem.get_from_tx_pin(), None);
+    }
+
+    /// Runs the ether, where a slow modem starts transmitting `slow_bytes` at
+    /// the first tick, and a fast modem writes `fast_bytes` at `fast_start` tick.
+    /// Returns the bytes received by the third modem.
+    fn run_slow_and_fast(
+        policy: crate::CollisionPolicy,
+        slow_bytes: &[u8],
+        fast_bytes: &[u8],
+        fast_start: usize,
+        ticks: usize,
+    ) -> Vec<u8> {
+        use super::EtherSimulator;
+        use crate::{DataRate, IODriverSimulator, WirelessModemFake};
+
+        let mut ether = EtherSimulator::new("ether");
+        ether.set_collision_policy(policy);
+
+        let slow_modem = WirelessModemFake::new("slow");
+        let fast_modem = WirelessModemFake::new("fast");
+        let receiving_modem = WirelessModemFake::new("receiver");
+
+        slow_modem.set_data_rate(DataRate::TicksPerByte(4));
+
+        ether.register_driver(slow_modem.clone());
+        ether.register_driver(fast_modem.clone());
+        ether.register_driver(receiving_modem.clone());
+        ether.set_signal_strength("slow", "receiver", -50.0);
+        ether.set_signal_strength("fast", "receiver", -80.0);
+
+        slow_modem.write(slow_bytes).unwrap();
+        for tick in 0..ticks {
+            if tick == fast_start {
+                fast_modem.write(fast_bytes).unwrap();
+            }
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+        }
+
+        let mut received = vec![];
+        while let Some(byte) = receiving_modem.get_from_tx_pin() {
+            received.push(byte);
+        }
+        received
+    }
+
+    #[test]
+    fn test_of_slow_transmission_occupying_ether() {
+        use super::EtherSimulator;
+        use crate::{DataRate, IODriverSimulator, WirelessModemFake};
+
+        let mut ether = EtherSimulator::new("ether");
+
+        let slow_modem = WirelessModemFake::new("slow");
+        let receiving_modem = WirelessModemFake::new("receiver");
+
+        slow_modem.set_data_rate(DataRate::TicksPerByte(5));
+
+        ether.register_driver(slow_modem.clone());
+        ether.register_driver(receiving_modem.clone());
+
+        slow_modem.write(b"s").unwrap();
+
+        let mut busy = vec![];
+        for _ in 0..6 {
+            ether.start_tick();
+            ether.simulate();
+            ether.end_tick();
+            busy.push(receiving_modem.channel_busy());
+        }
+
+        // Ether is busy for the whole airtime of the slow byte.
+        assert_eq!(busy, vec![true, true, true, true, true, false]);
+        assert_eq!(receiving_modem.get_from_tx_pin(), Some(b's'));
+    }
+
+    #[test]
+    fn test_of_collision_within_airtime_of_slow_byte() {
+        use crate::{CollisionCorruption, CollisionPolicy};
+
+        let destructive = CollisionPolicy::Destructive;
+
+        // Fast byte overlaps the middle of the slow one, so both are garbled.
+        // Slow byte is combined with the fast one, which completed during it's
+        // airtime, and the fast byte is combined with the noise of unfinished slow one.
+        let received = run_slow_and_fast(
+            destructive(CollisionCorruption::BitwiseOr),
+            b"s",
+            b"f",
+            2,
+            4,
+        );
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0] & b'f', b'f');
+        assert_eq!(received[1], b's' | b'f');
+
+        let received = run_slow_and_fast(
+            destructive(CollisionCorruption::BitwiseXor),
+            b"s",
+            b"f",
+            2,
+            4,
+        );
+        assert_eq!(received.len(), 2);
+        assert_ne!(received[0], b'f');
+        assert_eq!(received[1], b's' ^ b'f');
+
+        let received =
+            run_slow_and_fast(destructive(CollisionCorruption::Silence), b"s", b"f", 2, 4);
+        assert_eq!(received, vec![]);
+
+        // Without capture, capture policy garbles the same way.
+        let capture = |margin| CollisionPolicy::Capture {
+            margin,
+            corruption: CollisionCorruption::BitwiseOr,
+        };
+        let received = run_slow_and_fast(capture(40.0), b"s", b"f", 2, 4);
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[1], b's' | b'f');
+
+        // Stronger slow transmission captures the receiver for it's whole airtime.
+        assert_eq!(run_slow_and_fast(capture(3.0), b"s", b"f", 2, 4), b"s");
+
+        // Transmission in the middle of a byte never wins, and the slow byte,
+        // which has lost the arbitration, is dropped.
+        assert_eq!(
+            run_slow_and_fast(CollisionPolicy::LastBroadcasterYields, b"s", b"1234", 0, 4),
+            b"1234"
+        );
+        assert_eq!(
+            run_slow_and_fast(CollisionPolicy::Random, b"s", b"1234", 0, 4),
+            b"1234"
+        );
+    }
+
+    #[test]
+    fn test_of_overlapping_slow_transmissions() {
+        use super::EtherSimulator;
+        use crate::{
+            CollisionCorruption, CollisionPolicy, DataRate, IODriverSimulator, WirelessModemFake,
+        };
+
+        for corruption in [CollisionCorruption::Silence, CollisionCorruption::BitwiseOr] {
+            let mut ether = EtherSimulator::new("ether");
+            ether.set_collision_policy(CollisionPolicy::Destructive(corruption));
+
+            let modem_a = WirelessModemFake::new("a");
+            let modem_b = WirelessModemFake::new("b");
+            let receiving_modem = WirelessModemFake::new("receiver");
+
+            modem_a.set_data_rate(DataRate::TicksPerByte(4));
+            modem_b.set_data_rate(DataRate::TicksPerByte(4));
+
+            ether.register_driver(modem_a.clone());
+            ether.register_driver(modem_b.clone());
+            ether.register_driver(receiving_modem.clone());
+
+            modem_a.put_to_rx_pin(b'A');
+            let mut received = vec![];
+            for tick in 0..6 {
+                if tick == 2 {
+                    modem_b.put_to_rx_pin(b'B');
+                }
+                ether.start_tick();
+                ether.simulate();
+                ether.end_tick();
+                received.extend(receiving_modem.get_from_tx_pin());
+            }
+
+            // Both bytes are garbled, though only the ends of their airtime overlap.
+            if corruption == CollisionCorruption::Silence {
+                assert_eq!(received, vec![]);
+            } else {
+                assert_eq!(received.len(), 2);
+                assert_ne!(received[0], b'A');
+                assert_eq!(received[1] & (b'A' | b'B'), b'A' | b'B');
+            }
+        }
+    }
 }
@@ -1,7 +1,23 @@
+mod channel_model;
+mod collision_policy;
 mod device;
+mod error;
 mod ether_simulator;
+mod mobility;
 mod network_simulator;
+mod rng;
+mod simulation_clock;
+mod wire_simulator;
 
-pub use device::{IODriverSimulator, /*WiredModemFake,*/ WirelessModemFake};
+pub use channel_model::{GilbertElliottModel, PropagationModel};
+pub use collision_policy::{CollisionCorruption, CollisionPolicy};
+pub use device::{
+    AutoAckModemFake, DataRate, DriverHandle, IODriverSimulator, OverflowPolicy, PacketModemFake,
+    RateMismatchPolicy, TxReport, TxStatus, WiredModemFake, WirelessModemFake,
+};
+pub use error::SimulatorError;
 pub use ether_simulator::EtherSimulator;
+pub use mobility::{MobilityModel, Position};
 pub use network_simulator::NetworkSimulator;
+pub use simulation_clock::SimulationClock;
+pub use wire_simulator::WireSimulator;
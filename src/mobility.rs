@@ -0,0 +1,277 @@
+use crate::rng::SimulationRng;
+
+/// Coordinates of the device. Devices of 2D topologies just keep `z` at 0.
+/// Units are arbitrary, but shall be the same as the units of
+/// `PropagationModel` of the ether.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Position {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Position {
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Returns euclidean distance to other position.
+    /// ```
+    /// use proto_lab::Position;
+    ///
+    /// let a = Position::new(0.0, 0.0, 0.0);
+    /// let b = Position::new(3.0, 4.0, 0.0);
+    /// assert_eq!(a.distance_to(&b), 5.0);
+    /// ```
+    pub fn distance_to(&self, other: &Position) -> f64 {
+        let (dx, dy, dz) = (other.x - self.x, other.y - self.y, other.z - self.z);
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
+
+    /// Returns position moved towards `target` by `distance` at most.
+    fn moved_towards(&self, target: &Position, distance: f64) -> Position {
+        let full_distance = self.distance_to(target);
+        if full_distance <= distance {
+            return *target;
+        }
+        self.interpolated(target, distance / full_distance)
+    }
+
+    /// Returns position at `fraction` (0.0 ..= 1.0) of the way to `target`.
+    fn interpolated(&self, target: &Position, fraction: f64) -> Position {
+        Position {
+            x: self.x + (target.x - self.x) * fraction,
+            y: self.y + (target.y - self.y) * fraction,
+            z: self.z + (target.z - self.z) * fraction,
+        }
+    }
+
+    /// Returns the closest position within the area between two corners,
+    /// which can be given in any order.
+    fn clamped(&self, corner_1: &Position, corner_2: &Position) -> Position {
+        let clamp = |value: f64, bound_1: f64, bound_2: f64| {
+            value.clamp(bound_1.min(bound_2), bound_1.max(bound_2))
+        };
+        Position {
+            x: clamp(self.x, corner_1.x, corner_2.x),
+            y: clamp(self.y, corner_1.y, corner_2.y),
+            z: clamp(self.z, corner_1.z, corner_2.z),
+        }
+    }
+}
+
+/// Defines how the device moves, as the simulation runs.
+/// Position of the device is updated once per tick.
+/// See `EtherSimulator::set_mobility_model`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MobilityModel {
+    /// Device travels through the waypoints in the given order by `speed`
+    /// units per tick, and stays at the last one. Speed shall be positive and finite.
+    Waypoints {
+        waypoints: Vec<Position>,
+        speed: f64,
+    },
+    /// Device moves each tick by random offset of up to `max_step` units
+    /// along each axis, staying within the area between `min` and `max`
+    /// corners. Swapped bounds of `min` and `max` are accepted.
+    /// Moves are driven by the seeded generator of the ether.
+    RandomWalk {
+        max_step: f64,
+        min: Position,
+        max: Position,
+    },
+    /// Device follows the path given by `(tick, position)` key points,
+    /// moving linearly between them. Ticks are counted from the moment
+    /// the model is set, and key points shall be ordered by ticks.
+    ScriptedPath(Vec<(u64, Position)>),
+}
+
+impl MobilityModel {
+    /// Tells whether the device moves as the model describes.
+    pub(crate) fn is_valid(&self) -> bool {
+        match self {
+            MobilityModel::Waypoints { speed, .. } => speed.is_finite() && *speed > 0.0,
+            MobilityModel::RandomWalk { .. } | MobilityModel::ScriptedPath(_) => true,
+        }
+    }
+}
+
+/// Device, placed within the ether.
+#[derive(Default)]
+pub(crate) struct Node {
+    position: Position,
+    mobility: Option<MobilityModel>,
+    /// Count of ticks passed since the mobility model was set.
+    age: u64,
+    next_waypoint: usize,
+}
+
+impl Node {
+    pub(crate) fn get_position(&self) -> Position {
+        self.position
+    }
+
+    pub(crate) fn set_position(&mut self, position: Position) {
+        self.position = position;
+    }
+
+    pub(crate) fn get_mobility_model(&self) -> Option<&MobilityModel> {
+        self.mobility.as_ref()
+    }
+
+    pub(crate) fn set_mobility_model(&mut self, mobility: Option<MobilityModel>) {
+        self.age = 0;
+        self.next_waypoint = 0;
+        self.mobility = mobility;
+        if let Some(MobilityModel::ScriptedPath(path)) = self.mobility.as_ref() {
+            if let Some(position) = Self::scripted_position(path, 0) {
+                self.position = position;
+            }
+        }
+    }
+
+    /// Moves the device to it's position at the next tick.
+    pub(crate) fn step(&mut self, rng: &mut SimulationRng) {
+        self.age += 1;
+        match self.mobility.as_ref() {
+            None => {}
+            Some(MobilityModel::Waypoints { waypoints, speed }) => {
+                let mut remaining = *speed;
+                while let Some(target) = waypoints.get(self.next_waypoint) {
+                    let distance = self.position.distance_to(target);
+                    if distance > remaining {
+                        self.position = self.position.moved_towards(target, remaining);
+                        break;
+                    }
+                    self.position = *target;
+                    remaining -= distance;
+                    self.next_waypoint += 1;
+                }
+            }
+            Some(MobilityModel::RandomWalk { max_step, min, max }) => {
+                let mut offset = || (rng.next_f64() * 2.0 - 1.0) * max_step;
+                let moved = Position {
+                    x: self.position.x + offset(),
+                    y: self.position.y + offset(),
+                    z: self.position.z + offset(),
+                };
+                self.position = moved.clamped(min, max);
+            }
+            Some(MobilityModel::ScriptedPath(path)) => {
+                if let Some(position) = Self::scripted_position(path, self.age) {
+                    self.position = position;
+                }
+            }
+        }
+    }
+
+    fn scripted_position(path: &[(u64, Position)], tick: u64) -> Option<Position> {
+        let next = path.iter().position(|(key_tick, _)| *key_tick >= tick);
+        match next {
+            None => path.last().map(|(_, position)| *position),
+            Some(0) => path.first().map(|(_, position)| *position),
+            Some(index) => {
+                let (from_tick, from) = path[index - 1];
+                let (to_tick, to) = path[index];
+                let fraction = (tick - from_tick) as f64 / (to_tick - from_tick) as f64;
+                Some(from.interpolated(&to, fraction))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MobilityModel, Node, Position};
+    use crate::rng::SimulationRng;
+
+    #[test]
+    fn test_waypoints_are_visited_in_order() {
+        let mut rng = SimulationRng::new(0);
+        let mut node = Node::default();
+        node.set_mobility_model(Some(MobilityModel::Waypoints {
+            waypoints: vec![Position::new(2.0, 0.0, 0.0), Position::new(2.0, 3.0, 0.0)],
+            speed: 1.5,
+        }));
+
+        let mut path = vec![];
+        for _ in 0..5 {
+            node.step(&mut rng);
+            path.push(node.get_position());
+        }
+
+        assert_eq!(
+            path,
+            vec![
+                Position::new(1.5, 0.0, 0.0),
+                Position::new(2.0, 1.0, 0.0),
+                Position::new(2.0, 2.5, 0.0),
+                Position::new(2.0, 3.0, 0.0),
+                Position::new(2.0, 3.0, 0.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_waypoints_need_positive_speed() {
+        let waypoints = |speed| MobilityModel::Waypoints {
+            waypoints: vec![Position::new(1.0, 0.0, 0.0)],
+            speed,
+        };
+
+        assert!(waypoints(0.5).is_valid());
+        for speed in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(!waypoints(speed).is_valid());
+        }
+    }
+
+    #[test]
+    fn test_random_walk_stays_within_area() {
+        let mut rng = SimulationRng::new(0);
+        let (min, max) = (Position::new(0.0, 0.0, 0.0), Position::new(10.0, 10.0, 0.0));
+        let mut node = Node::default();
+        node.set_mobility_model(Some(MobilityModel::RandomWalk {
+            max_step: 3.0,
+            min,
+            max,
+        }));
+
+        for step in 0..2000 {
+            // Area is the same with swapped corners.
+            if step == 1000 {
+                node.set_mobility_model(Some(MobilityModel::RandomWalk {
+                    max_step: 3.0,
+                    min: max,
+                    max: min,
+                }));
+            }
+
+            let before = node.get_position();
+            node.step(&mut rng);
+            let after = node.get_position();
+
+            assert!((0.0..=10.0).contains(&after.x) && (0.0..=10.0).contains(&after.y));
+            assert_eq!(after.z, 0.0);
+            assert!((after.x - before.x).abs() <= 3.0 && (after.y - before.y).abs() <= 3.0);
+        }
+    }
+
+    #[test]
+    fn test_scripted_path_is_interpolated() {
+        let mut rng = SimulationRng::new(0);
+        let mut node = Node::default();
+        node.set_mobility_model(Some(MobilityModel::ScriptedPath(vec![
+            (0, Position::new(0.0, 0.0, 0.0)),
+            (4, Position::new(8.0, 0.0, 0.0)),
+        ])));
+        assert_eq!(node.get_position(), Position::new(0.0, 0.0, 0.0));
+
+        node.step(&mut rng);
+        assert_eq!(node.get_position(), Position::new(2.0, 0.0, 0.0));
+
+        for _ in 0..10 {
+            node.step(&mut rng);
+        }
+        assert_eq!(node.get_position(), Position::new(8.0, 0.0, 0.0));
+    }
+}
@@ -1,149 +1,853 @@
 use std::{
-    cell::RefCell,
-    ops::DerefMut,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Condvar, Mutex, MutexGuard, PoisonError,
+    },
+    time::{Duration, Instant},
 };
 
-use crate::EtherSimulator;
+use crate::{EtherSimulator, SimulationClock, SimulatorError, WireSimulator};
+
+/// Settings of the simulation thread, which can be changed while it is running.
+struct ThreadControl {
+    killed: AtomicBool,
+    /// Set once the simulation thread has exited, either stopped or panicked.
+    dead: AtomicBool,
+    ms_per_tick: AtomicU64,
+    paused: AtomicBool,
+    pending_steps: Mutex<u64>,
+    steps_done: Condvar,
+}
+
+impl ThreadControl {
+    fn is_killed(&self) -> bool {
+        self.killed.load(Ordering::Acquire)
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Acquire)
+    }
+
+    fn is_dead(&self) -> bool {
+        self.dead.load(Ordering::Acquire)
+    }
+
+    /// Waits for the next tick. Unlike sleeping - the wait is interrupted
+    /// as soon as the thread is killed or paused, and unparked by the simulator.
+    fn wait_for_next_tick(&self) {
+        let ms_per_tick = self.ms_per_tick.load(Ordering::Acquire);
+        let deadline = Instant::now() + Duration::from_millis(ms_per_tick);
+
+        while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+            if remaining.is_zero() || self.is_killed() || self.is_paused() {
+                break;
+            }
+            std::thread::park_timeout(remaining);
+        }
+    }
+
+    /// Tells if there is a requested step to be done by paused thread.
+    fn has_pending_step(&self) -> bool {
+        *self
+            .pending_steps
+            .lock()
+            .expect("Fail to get lock on pending steps")
+            > 0
+    }
+
+    fn finish_step(&self) {
+        let mut pending_steps = self
+            .pending_steps
+            .lock()
+            .expect("Fail to get lock on pending steps");
+        *pending_steps = pending_steps.saturating_sub(1);
+        self.steps_done.notify_all();
+    }
+
+    fn cancel_steps(&self) {
+        *self
+            .pending_steps
+            .lock()
+            .expect("Fail to get lock on pending steps") = 0;
+        self.steps_done.notify_all();
+    }
+
+    /// Marks the thread as dead, and wakes up everyone waiting for steps.
+    fn mark_dead(&self) {
+        self.dead.store(true, Ordering::Release);
+        // Notify under the lock, so a waiter can not miss it between checking
+        // the flag and starting to wait.
+        let _pending_steps = self
+            .pending_steps
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        self.steps_done.notify_all();
+    }
+}
+
+/// Marks the simulation thread as dead when it exits, even by panic of a device.
+struct DeathGuard(Arc<ThreadControl>);
+
+impl Drop for DeathGuard {
+    fn drop(&mut self) {
+        self.0.mark_dead();
+    }
+}
 
 pub struct NetworkSimulator {
-    ethers: RefCell<Option<Vec<EtherSimulator>>>,
-    ms_per_tick: u64,
-    simulation_thread_handle: Option<std::thread::JoinHandle<Vec<EtherSimulator>>>,
-    thread_killer: Arc<Mutex<bool>>,
+    ethers: Arc<Mutex<Vec<EtherSimulator>>>,
+    wires: Arc<Mutex<Vec<WireSimulator>>>,
+    control: Arc<ThreadControl>,
+    clock: SimulationClock,
+    simulation_thread_handle: Option<std::thread::JoinHandle<()>>,
 }
 
 /// NetworkSimulator is designed to simulate the network which consist of 1+ ethers.
 /// Each ether is instance of EtherSimulator
+/// Point-to-point links between two devices are simulated by wires,
+/// each wire is instance of WireSimulator
+///
+/// Ethers and wires can be created, removed and looked up at any moment,
+/// even while the simulation thread is running. Each tick is simulated
+/// while holding the topology, so the changes take effect at a tick boundary.
 impl NetworkSimulator {
+    /// Creates the simulator, which runs a tick each `ms_per_tick` real milliseconds
+    /// while the simulation thread is started.
+    /// With `ms_per_tick` set to 0 the thread runs ticks as fast as possible.
+    /// The simulated clock is advanced by `ms_per_tick` (at least 1) per tick by default,
+    /// which can be changed with `SimulationClock::set_ms_per_tick`.
     pub fn new(ms_per_tick: u64) -> Self {
         NetworkSimulator {
-            ethers: RefCell::new(Some(Vec::new())),
-            ms_per_tick,
+            ethers: Arc::new(Mutex::new(Vec::new())),
+            wires: Arc::new(Mutex::new(Vec::new())),
+            control: Arc::new(ThreadControl {
+                killed: AtomicBool::new(false),
+                dead: AtomicBool::new(false),
+                ms_per_tick: AtomicU64::new(ms_per_tick),
+                paused: AtomicBool::new(false),
+                pending_steps: Mutex::new(0),
+                steps_done: Condvar::new(),
+            }),
+            clock: SimulationClock::new(ms_per_tick.max(1)),
             simulation_thread_handle: None,
-            thread_killer: Arc::new(Mutex::new(false)),
         }
     }
 
+    /// Returns the simulated clock of the network.
+    /// The clock is shared, so it can be moved to other threads and queried
+    /// while the simulation thread is running.
+    /// ```
+    /// use proto_lab::NetworkSimulator;
+    ///
+    /// let simulator = NetworkSimulator::new(1);
+    /// assert_eq!(simulator.get_clock().get_elapsed_ms(), 0);
+    /// ```
+    pub fn get_clock(&self) -> SimulationClock {
+        self.clock.clone()
+    }
+
+    /// Creates a new ether.
+    /// Can be done while the simulation thread is running,
+    /// the ether starts being simulated from the next tick.
+    /// ```
+    /// use proto_lab::{NetworkSimulator, WirelessModemFake};
+    ///
+    /// let mut simulator = NetworkSimulator::new(1);
+    /// simulator.start_simulation_thread();
+    ///
+    /// simulator.create_ether("1");
+    /// simulator
+    ///     .get_ether("1")
+    ///     .expect("No ether")
+    ///     .register_driver(WirelessModemFake::new("modem"));
+    ///
+    /// simulator.stop_simulation_thread();
+    /// ```
     pub fn create_ether(&self, name: &str) {
-        match self.ethers.borrow_mut().deref_mut() {
-            Some(ref mut ethers) => {
-                let new_ether = EtherSimulator::new(name);
-                ethers.push(new_ether);
-            }
-            None => {
-                panic!("Simulation thread is already started. Can not change configuration")
-            }
-        };
+        let mut ethers = lock_topology(&self.ethers);
+        ethers.push(EtherSimulator::new(name));
     }
 
+    /// Gets the ether by it's name.
     pub fn get_ether(&self, name: &str) -> Option<EtherSimulator> {
-        match self.ethers.borrow_mut().deref_mut() {
-            None => panic!("Simulation thread is started. Can not get ether"),
-            Some(ref ethers) => {
-                for ether in ethers.iter() {
-                    if ether.get_name() == name {
-                        return Some(ether.clone());
-                    }
-                }
-                None
+        let ethers = lock_topology(&self.ethers);
+        ethers
+            .iter()
+            .find(|ether| ether.get_name() == name)
+            .cloned()
+    }
+
+    /// Removes the ether by it's name.
+    /// Can be done while the simulation thread is running,
+    /// the ether is not simulated starting from the next tick.
+    /// ```
+    /// use proto_lab::NetworkSimulator;
+    ///
+    /// let simulator = NetworkSimulator::new(1);
+    /// simulator.create_ether("1");
+    /// simulator.remove_ether("1");
+    /// assert!(simulator.get_ether("1").is_none());
+    /// ```
+    pub fn remove_ether(&self, name: &str) {
+        let mut ethers = lock_topology(&self.ethers);
+        ethers.retain(|ether| {
+            if ether.get_name() != name {
+                return true;
             }
-        }
+            // In case if ether is removed in the middle of manual tick.
+            ether.end_tick();
+            false
+        });
     }
 
-    pub fn start_tick(&self) {
-        match self.ethers.borrow_mut().deref_mut() {
-            None => panic!(
-                "Simulation thread is started. Can not do start_tick and thread at the same time"
-            ),
-            Some(ref ethers) => {
-                for ether in ethers.iter() {
-                    ether.start_tick();
-                }
+    /// Creates a point-to-point wire, which can connect two devices.
+    /// Can be done while the simulation thread is running,
+    /// the wire starts being simulated from the next tick.
+    /// ```
+    /// use proto_lab::{NetworkSimulator, WiredModemFake};
+    ///
+    /// let simulator = NetworkSimulator::new(1);
+    /// simulator.create_wire("uart");
+    ///
+    /// let mut wire = simulator.get_wire("uart").expect("No wire");
+    /// wire.connect_driver(WiredModemFake::new("board_1"));
+    /// wire.connect_driver(WiredModemFake::new("board_2"));
+    /// ```
+    pub fn create_wire(&self, name: &str) {
+        let mut wires = lock_topology(&self.wires);
+        wires.push(WireSimulator::new(name));
+    }
+
+    /// Gets the wire by it's name.
+    pub fn get_wire(&self, name: &str) -> Option<WireSimulator> {
+        let wires = lock_topology(&self.wires);
+        wires.iter().find(|wire| wire.get_name() == name).cloned()
+    }
+
+    /// Removes the wire by it's name.
+    /// Can be done while the simulation thread is running,
+    /// the wire is not simulated starting from the next tick.
+    pub fn remove_wire(&self, name: &str) {
+        let mut wires = lock_topology(&self.wires);
+        wires.retain(|wire| {
+            if wire.get_name() != name {
+                return true;
             }
-        }
+            // In case if wire is removed in the middle of manual tick.
+            wire.end_tick();
+            false
+        });
+    }
+
+    /// Panics if the simulation thread is started, see `try_start_tick`.
+    pub fn start_tick(&self) {
+        self.try_start_tick()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Prepares all the ethers and wires for starting of simulation during tick.
+    /// Manual ticking is not possible while the simulation thread is started.
+    pub fn try_start_tick(&self) -> Result<(), SimulatorError> {
+        self.ensure_manual_ticking()?;
+        start_tick(&self.ethers, &self.wires);
+        Ok(())
     }
 
+    /// Panics if the simulation thread is started, see `try_end_tick`.
     pub fn end_tick(&self) {
-        match self.ethers.borrow_mut().deref_mut() {
-            None => panic!(
-                "Simulation thread is started. Can not do start_tick and thread at the same time"
-            ),
-            Some(ref ethers) => {
-                for ether in ethers.iter() {
-                    ether.end_tick();
-                }
+        self.try_end_tick()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Prepares all the ethers and wires for ending of simulation during tick,
+    /// and moves the simulated clock forward.
+    /// Manual ticking is not possible while the simulation thread is started.
+    pub fn try_end_tick(&self) -> Result<(), SimulatorError> {
+        self.ensure_manual_ticking()?;
+        end_tick(&self.ethers, &self.wires, &self.clock);
+        Ok(())
+    }
+
+    /// Panics if the simulation thread is started, see `try_simulate`.
+    pub fn simulate(&self) {
+        self.try_simulate()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Transfers the data within all the ethers and wires.
+    /// Manual ticking is not possible while the simulation thread is started.
+    /// ```
+    /// use proto_lab::{NetworkSimulator, SimulatorError};
+    ///
+    /// let mut simulator = NetworkSimulator::new(1);
+    /// simulator.start_simulation_thread();
+    /// assert_eq!(
+    ///     simulator.try_simulate(),
+    ///     Err(SimulatorError::SimulationThreadAlreadyStarted)
+    /// );
+    /// simulator.stop_simulation_thread();
+    /// ```
+    pub fn try_simulate(&self) -> Result<(), SimulatorError> {
+        self.ensure_manual_ticking()?;
+        simulate(&self.ethers, &self.wires);
+        Ok(())
+    }
+
+    fn ensure_manual_ticking(&self) -> Result<(), SimulatorError> {
+        match self.simulation_thread_handle {
+            Some(_) => Err(SimulatorError::SimulationThreadAlreadyStarted),
+            None => Ok(()),
+        }
+    }
+
+    /// Runs exactly `count` ticks in the calling thread without any sleeping.
+    /// ```
+    /// use proto_lab::{IODriverSimulator, NetworkSimulator, WirelessModemFake};
+    ///
+    /// let simulator = NetworkSimulator::new(1);
+    /// simulator.create_ether("ether");
+    ///
+    /// let modem_1 = WirelessModemFake::new("modem_1");
+    /// let modem_2 = WirelessModemFake::new("modem_2");
+    /// let mut ether = simulator.get_ether("ether").unwrap();
+    /// ether.register_driver(modem_1.clone());
+    /// ether.register_driver(modem_2.clone());
+    ///
+    /// modem_1.put_to_rx_pin(b'a');
+    /// modem_1.put_to_rx_pin(b'b');
+    ///
+    /// simulator.run_ticks(2);
+    ///
+    /// assert_eq!(modem_2.get_from_tx_pin(), Some(b'a'));
+    /// assert_eq!(modem_2.get_from_tx_pin(), Some(b'b'));
+    /// assert_eq!(simulator.get_clock().get_ticks(), 2);
+    /// ```
+    pub fn run_ticks(&self, count: u64) {
+        self.try_run_ticks(count)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Runs exactly `count` ticks in the calling thread without any sleeping.
+    pub fn try_run_ticks(&self, count: u64) -> Result<(), SimulatorError> {
+        for _ in 0..count {
+            self.try_start_tick()?;
+            self.try_simulate()?;
+            self.try_end_tick()?;
+        }
+        Ok(())
+    }
+
+    /// Runs ticks in the calling thread without any sleeping until `predicate` returns true.
+    /// The predicate is checked before the first tick and after each tick.
+    /// Returns the count of ticks taken, or `SimulatorError::Timeout`
+    /// if the predicate is still false after `max_ticks` ticks,
+    /// or `SimulatorError::SimulationThreadAlreadyStarted` if ticks are run by the thread.
+    /// ```
+    /// use proto_lab::{IODriverSimulator, NetworkSimulator, SimulatorError, WirelessModemFake};
+    ///
+    /// let simulator = NetworkSimulator::new(1);
+    /// simulator.create_ether("ether");
+    ///
+    /// let modem_1 = WirelessModemFake::new("modem_1");
+    /// let modem_2 = WirelessModemFake::new("modem_2");
+    /// let mut ether = simulator.get_ether("ether").unwrap();
+    /// ether.register_driver(modem_1.clone());
+    /// ether.register_driver(modem_2.clone());
+    ///
+    /// modem_1.write(b"abc").unwrap();
+    ///
+    /// let mut received = vec![];
+    /// let ticks = simulator.run_until(10, || {
+    ///     while let Some(byte) = modem_2.get_from_tx_pin() {
+    ///         received.push(byte);
+    ///     }
+    ///     received.len() == 3
+    /// });
+    ///
+    /// assert_eq!(ticks, Ok(3));
+    /// assert_eq!(simulator.run_until(5, || false), Err(SimulatorError::Timeout { ticks: 5 }));
+    /// ```
+    pub fn run_until<F>(&self, max_ticks: u64, mut predicate: F) -> Result<u64, SimulatorError>
+    where
+        F: FnMut() -> bool,
+    {
+        if predicate() {
+            return Ok(0);
+        }
+        for tick in 1..=max_ticks {
+            self.try_run_ticks(1)?;
+            if predicate() {
+                return Ok(tick);
             }
         }
+        Err(SimulatorError::Timeout { ticks: max_ticks })
     }
 
-    pub fn simulate(&self) {
-        match self.ethers.borrow_mut().deref_mut() {
-            None => panic!(
-                "Simulation thread is started. Can not do start_tick and thread at the same time"
-            ),
-            Some(ref mut ethers) => {
-                for ether in ethers.iter_mut() {
-                    ether.simulate();
-                }
+    /// Sets how many real milliseconds the simulation thread waits between ticks.
+    /// 0 makes the thread to run ticks as fast as possible.
+    /// Can be changed while the simulation thread is running.
+    pub fn set_ms_per_tick(&self, ms_per_tick: u64) {
+        self.control
+            .ms_per_tick
+            .store(ms_per_tick, Ordering::Release);
+    }
+
+    /// Returns how many real milliseconds the simulation thread waits between ticks.
+    pub fn get_ms_per_tick(&self) -> u64 {
+        self.control.ms_per_tick.load(Ordering::Acquire)
+    }
+
+    /// Pauses the simulation thread at the tick boundary, without stopping it.
+    /// ```
+    /// use proto_lab::{NetworkSimulator, SimulatorError};
+    ///
+    /// let mut simulator = NetworkSimulator::new(0);
+    /// assert_eq!(simulator.pause(), Err(SimulatorError::SimulationThreadNotStarted));
+    ///
+    /// simulator.start_simulation_thread();
+    /// simulator.pause().unwrap();
+    /// assert!(simulator.is_paused());
+    ///
+    /// let clock = simulator.get_clock();
+    /// let ticks = clock.get_ticks();
+    /// std::thread::sleep(std::time::Duration::from_millis(10));
+    /// assert_eq!(clock.get_ticks(), ticks);
+    ///
+    /// simulator.resume().unwrap();
+    /// simulator.stop_simulation_thread();
+    /// ```
+    pub fn pause(&self) -> Result<(), SimulatorError> {
+        let simulation_thread_handle = self
+            .simulation_thread_handle
+            .as_ref()
+            .ok_or(SimulatorError::SimulationThreadNotStarted)?;
+
+        // Wait for currently running tick to be finished,
+        // so after return no more ticks are done.
+        let ethers = lock_topology(&self.ethers);
+        self.control.paused.store(true, Ordering::Release);
+        drop(ethers);
+
+        simulation_thread_handle.thread().unpark();
+        Ok(())
+    }
+
+    /// Resumes the paused simulation thread.
+    pub fn resume(&self) -> Result<(), SimulatorError> {
+        let simulation_thread_handle = self
+            .simulation_thread_handle
+            .as_ref()
+            .ok_or(SimulatorError::SimulationThreadNotStarted)?;
+
+        self.control.paused.store(false, Ordering::Release);
+        self.control.cancel_steps();
+        simulation_thread_handle.thread().unpark();
+        Ok(())
+    }
+
+    /// Tells if the simulation thread is paused.
+    pub fn is_paused(&self) -> bool {
+        self.simulation_thread_handle.is_some() && self.control.is_paused()
+    }
+
+    /// Makes paused simulation thread to run exactly `count` ticks,
+    /// and waits for them to be finished. The thread stays paused after that.
+    /// Returns `SimulatorError::SimulationThreadPanicked` if a device panics
+    /// within these ticks.
+    /// ```
+    /// use proto_lab::{NetworkSimulator, SimulatorError};
+    ///
+    /// let mut simulator = NetworkSimulator::new(0);
+    /// simulator.start_simulation_thread();
+    /// assert_eq!(simulator.step(1), Err(SimulatorError::SimulationThreadNotPaused));
+    ///
+    /// simulator.pause().unwrap();
+    /// let clock = simulator.get_clock();
+    /// let ticks = clock.get_ticks();
+    ///
+    /// simulator.step(5).unwrap();
+    /// assert_eq!(clock.get_ticks(), ticks + 5);
+    ///
+    /// simulator.stop_simulation_thread();
+    /// ```
+    pub fn step(&self, count: u64) -> Result<(), SimulatorError> {
+        let simulation_thread_handle = self
+            .simulation_thread_handle
+            .as_ref()
+            .ok_or(SimulatorError::SimulationThreadNotStarted)?;
+
+        if self.control.is_dead() {
+            return Err(SimulatorError::SimulationThreadPanicked);
+        }
+        if !self.control.is_paused() {
+            return Err(SimulatorError::SimulationThreadNotPaused);
+        }
+
+        let mut pending_steps = self
+            .control
+            .pending_steps
+            .lock()
+            .map_err(|_| SimulatorError::SimulationThreadPanicked)?;
+        *pending_steps += count;
+        simulation_thread_handle.thread().unpark();
+
+        while *pending_steps > 0 {
+            if self.control.is_dead() {
+                return Err(SimulatorError::SimulationThreadPanicked);
             }
+            pending_steps = self
+                .control
+                .steps_done
+                .wait(pending_steps)
+                .map_err(|_| SimulatorError::SimulationThreadPanicked)?;
         }
+        Ok(())
     }
 
+    /// Starts the thread, which runs ticks in background.
+    /// Panics if the thread is already started, see `try_start_simulation_thread`.
     pub fn start_simulation_thread(&mut self) {
+        self.try_start_simulation_thread()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Starts the thread, which runs ticks in background.
+    /// ```
+    /// use proto_lab::{NetworkSimulator, SimulatorError};
+    ///
+    /// let mut simulator = NetworkSimulator::new(1);
+    /// assert_eq!(simulator.try_start_simulation_thread(), Ok(()));
+    /// assert_eq!(
+    ///     simulator.try_start_simulation_thread(),
+    ///     Err(SimulatorError::SimulationThreadAlreadyStarted)
+    /// );
+    /// simulator.stop_simulation_thread();
+    /// ```
+    pub fn try_start_simulation_thread(&mut self) -> Result<(), SimulatorError> {
         match self.simulation_thread_handle {
-            Some(_) => panic!("Simulation thread is already started"),
+            Some(_) => Err(SimulatorError::SimulationThreadAlreadyStarted),
             None => {
-                let mut ethers = self.ethers.take().unwrap();
+                let ethers = Arc::clone(&self.ethers);
+                let wires = Arc::clone(&self.wires);
 
-                let ms_per_tick = self.ms_per_tick;
-                let thread_killer_clone = Arc::clone(&self.thread_killer);
+                let control = Arc::clone(&self.control);
+                let clock = self.clock.clone();
 
-                *self
-                    .thread_killer
-                    .lock()
-                    .expect("Fail to get lock on thread killer") = false;
+                self.control.killed.store(false, Ordering::Release);
+                self.control.dead.store(false, Ordering::Release);
 
                 self.simulation_thread_handle = Some(std::thread::spawn(move || {
+                    let _death_guard = DeathGuard(Arc::clone(&control));
                     loop {
-                        if *thread_killer_clone
-                            .lock()
-                            .expect("Faild to get lock on clonned thread killer")
-                        {
+                        if control.is_killed() {
                             break;
                         }
-                        std::thread::sleep(std::time::Duration::from_millis(ms_per_tick));
-                        for ether in ethers.iter_mut() {
-                            ether.start_tick();
-                        }
-                        for ether in ethers.iter_mut() {
-                            ether.simulate();
+                        if control.is_paused() {
+                            if control.has_pending_step() {
+                                let mut locked_ethers = lock_topology(&ethers);
+                                let mut locked_wires = lock_topology(&wires);
+                                run_tick(&mut locked_ethers, &mut locked_wires, &clock);
+                                control.finish_step();
+                            } else {
+                                std::thread::park();
+                            }
+                            continue;
                         }
-                        for ether in ethers.iter_mut() {
-                            ether.end_tick();
+                        control.wait_for_next_tick();
+                        let mut locked_ethers = lock_topology(&ethers);
+                        // Pause or stop could be requested while waiting. It is checked under the lock
+                        // of ethers, so no tick is done after `pause` has returned.
+                        if control.is_killed() || control.is_paused() {
+                            continue;
                         }
+                        let mut locked_wires = lock_topology(&wires);
+                        run_tick(&mut locked_ethers, &mut locked_wires, &clock);
                     }
-                    ethers
                 }));
+                Ok(())
             }
         }
     }
 
+    /// Stops the thread, which runs ticks in background.
+    /// Panics if the thread is not started, see `try_stop_simulation_thread`.
     pub fn stop_simulation_thread(&mut self) {
-        self.simulation_thread_handle = match self.simulation_thread_handle.take() {
-            None => panic!("Simulation thread is not started"),
+        self.try_stop_simulation_thread()
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Stops the thread, which runs ticks in background.
+    /// ```
+    /// use proto_lab::{NetworkSimulator, SimulatorError};
+    ///
+    /// let mut simulator = NetworkSimulator::new(1);
+    /// assert_eq!(
+    ///     simulator.try_stop_simulation_thread(),
+    ///     Err(SimulatorError::SimulationThreadNotStarted)
+    /// );
+    /// ```
+    pub fn try_stop_simulation_thread(&mut self) -> Result<(), SimulatorError> {
+        match self.simulation_thread_handle.take() {
+            None => Err(SimulatorError::SimulationThreadNotStarted),
             Some(simulation_thread_handle) => {
-                *self
-                    .thread_killer
-                    .lock()
-                    .expect("Fail to get lock on thread killer") = true;
-                self.ethers.replace(Some(
-                    simulation_thread_handle
-                        .join()
-                        .expect(" Fail to join simulation thread to get ethers back"),
-                ));
-                None
+                self.control.killed.store(true, Ordering::Release);
+                self.control.paused.store(false, Ordering::Release);
+                self.control.cancel_steps();
+                simulation_thread_handle.thread().unpark();
+                simulation_thread_handle
+                    .join()
+                    .map_err(|_| SimulatorError::SimulationThreadPanicked)
             }
+        }
+    }
+}
+
+impl Drop for NetworkSimulator {
+    /// Stops and joins the simulation thread, so it does not outlive the simulator,
+    /// even if the owner of the simulator has panicked.
+    fn drop(&mut self) {
+        // Thread is either not started, or has already panicked. Nothing to clean up.
+        let _ = self.try_stop_simulation_thread();
+    }
+}
+
+/// Locks the list of ethers or wires. A device, panicking within the tick,
+/// poisons the lock but leaves the list intact, so the topology stays usable,
+/// while the panic is reported by `step` and `try_stop_simulation_thread`.
+fn lock_topology<T>(topology: &Mutex<T>) -> MutexGuard<'_, T> {
+    topology.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn start_tick(ethers: &Mutex<Vec<EtherSimulator>>, wires: &Mutex<Vec<WireSimulator>>) {
+    let ethers = lock_topology(ethers);
+    let wires = lock_topology(wires);
+
+    for ether in ethers.iter() {
+        ether.start_tick();
+    }
+    for wire in wires.iter() {
+        wire.start_tick();
+    }
+}
+
+fn simulate(ethers: &Mutex<Vec<EtherSimulator>>, wires: &Mutex<Vec<WireSimulator>>) {
+    let mut ethers = lock_topology(ethers);
+    let mut wires = lock_topology(wires);
+
+    for ether in ethers.iter_mut() {
+        ether.simulate();
+    }
+    for wire in wires.iter_mut() {
+        wire.simulate();
+    }
+}
+
+fn end_tick(
+    ethers: &Mutex<Vec<EtherSimulator>>,
+    wires: &Mutex<Vec<WireSimulator>>,
+    clock: &SimulationClock,
+) {
+    let ethers = lock_topology(ethers);
+    let wires = lock_topology(wires);
+
+    for ether in ethers.iter() {
+        ether.end_tick();
+    }
+    for wire in wires.iter() {
+        wire.end_tick();
+    }
+    clock.advance();
+}
+
+/// Runs the whole tick. Shall be called while holding the topology,
+/// so changes of topology can only happen at a tick boundary.
+fn run_tick(ethers: &mut [EtherSimulator], wires: &mut [WireSimulator], clock: &SimulationClock) {
+    for ether in ethers.iter() {
+        ether.start_tick();
+    }
+    for wire in wires.iter() {
+        wire.start_tick();
+    }
+    for ether in ethers.iter_mut() {
+        ether.simulate();
+    }
+    for wire in wires.iter_mut() {
+        wire.simulate();
+    }
+    for ether in ethers.iter() {
+        ether.end_tick();
+    }
+    for wire in wires.iter() {
+        wire.end_tick();
+    }
+    clock.advance();
+}
+
+#[cfg(test)]
+mod test {
+    use super::NetworkSimulator;
+    use crate::{IODriverSimulator, WirelessModemFake};
+
+    #[test]
+    fn test_topology_change_while_thread_is_running() {
+        let mut simulator = NetworkSimulator::new(0);
+        let clock = simulator.get_clock();
+
+        simulator.start_simulation_thread();
+
+        simulator.create_ether("ether");
+        let modem_1 = WirelessModemFake::new("modem_1");
+        let modem_2 = WirelessModemFake::new("modem_2");
+        {
+            let mut ether = simulator.get_ether("ether").unwrap();
+            ether.register_driver(modem_1.clone());
+            ether.register_driver(modem_2.clone());
+        }
+
+        modem_1.write(b"hot").unwrap();
+
+        let ticks_at_start = clock.get_ticks();
+        while clock.get_ticks() < ticks_at_start + 100 {
+            std::thread::yield_now();
+        }
+
+        simulator.remove_ether("ether");
+        assert!(simulator.get_ether("ether").is_none());
+
+        simulator.stop_simulation_thread();
+
+        let mut received = [0u8; 3];
+        assert_eq!(modem_2.read(&mut received), Ok(3));
+        assert_eq!(&received, b"hot");
+    }
+
+    #[test]
+    fn test_unthrottled_simulation_thread() {
+        let mut simulator = NetworkSimulator::new(0);
+        simulator.create_ether("ether");
+
+        let modem_1 = WirelessModemFake::new("modem_1");
+        let modem_2 = WirelessModemFake::new("modem_2");
+        {
+            let mut ether = simulator.get_ether("ether").unwrap();
+            ether.register_driver(modem_1.clone());
+            ether.register_driver(modem_2.clone());
+        }
+
+        let clock = simulator.get_clock();
+        modem_1.write(&[7; 100]).unwrap();
+
+        simulator.start_simulation_thread();
+        while clock.get_ticks() < 1000 {
+            std::thread::yield_now();
+        }
+        simulator.stop_simulation_thread();
+
+        let mut received = [0u8; 100];
+        assert_eq!(modem_2.read(&mut received), Ok(100));
+        assert!(!modem_2.readable());
+    }
+
+    #[test]
+    fn test_drop_stops_slow_simulation_thread() {
+        let mut simulator = NetworkSimulator::new(60_000);
+        let clock = simulator.get_clock();
+        simulator.start_simulation_thread();
+
+        let started_at = std::time::Instant::now();
+        drop(simulator);
+
+        assert!(started_at.elapsed() < std::time::Duration::from_secs(10));
+        assert_eq!(clock.get_ticks(), 0);
+    }
+
+    #[test]
+    fn test_drop_on_panic_stops_simulation_thread() {
+        let (clock_sender, clock_receiver) = std::sync::mpsc::channel();
+
+        let result = std::thread::spawn(move || {
+            let mut simulator = NetworkSimulator::new(0);
+            clock_sender.send(simulator.get_clock()).unwrap();
+            simulator.start_simulation_thread();
+            panic!("Simulation timeout");
+        })
+        .join();
+        assert!(result.is_err());
+
+        let clock = clock_receiver.recv().unwrap();
+        let ticks = clock.get_ticks();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        assert_eq!(clock.get_ticks(), ticks);
+    }
+
+    #[test]
+    fn test_step_reports_panic_of_device() {
+        use crate::SimulatorError;
+        use std::sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
         };
+
+        // Device which panics at the start of the tick, once armed.
+        struct FaultyDevice {
+            armed: Arc<AtomicBool>,
+        }
+
+        impl IODriverSimulator for FaultyDevice {
+            fn get_from_device_network_side(&self) -> Option<u8> {
+                None
+            }
+            fn put_to_device_network_side(&self, _byte: u8) {}
+            fn get_from_tx_pin(&self) -> Option<u8> {
+                None
+            }
+            fn put_to_rx_pin(&self, _byte: u8) {}
+            fn start_tick(&self) {
+                if self.armed.load(Ordering::Acquire) {
+                    panic!("Device failure");
+                }
+            }
+            fn end_tick(&self) {}
+            fn readable(&self) -> bool {
+                false
+            }
+            fn writable(&self) -> bool {
+                false
+            }
+            fn get_name(&self) -> &str {
+                "faulty"
+            }
+        }
+
+        let armed = Arc::new(AtomicBool::new(false));
+        let mut simulator = NetworkSimulator::new(0);
+        simulator.create_ether("ether");
+        simulator
+            .get_ether("ether")
+            .unwrap()
+            .register_driver(FaultyDevice {
+                armed: Arc::clone(&armed),
+            });
+
+        simulator.start_simulation_thread();
+        simulator.pause().unwrap();
+        armed.store(true, Ordering::Release);
+
+        assert_eq!(
+            simulator.step(1),
+            Err(SimulatorError::SimulationThreadPanicked)
+        );
+        assert_eq!(
+            simulator.step(1),
+            Err(SimulatorError::SimulationThreadPanicked)
+        );
+
+        // Topology is still usable after the panic.
+        simulator.create_ether("other");
+        assert!(simulator.get_ether("ether").is_some());
+        assert_eq!(
+            simulator.try_stop_simulation_thread(),
+            Err(SimulatorError::SimulationThreadPanicked)
+        );
     }
 }
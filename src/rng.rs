@@ -0,0 +1,75 @@
+/// Small pseudo-random generator (SplitMix64), used to make random
+/// decisions of the simulation reproducible by the seed.
+/// Own implementation is used on purpose, so the sequence for the same seed
+/// never changes with the versions of external crates.
+#[derive(Clone, Debug)]
+pub(crate) struct SimulationRng {
+    state: u64,
+}
+
+impl SimulationRng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number within `0.0..1.0`.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns true with given probability.
+    /// Does not consume randomness for probability of 0, so links which are
+    /// not configured do not affect the random decisions of other links.
+    pub(crate) fn chance(&mut self, probability: f64) -> bool {
+        probability > 0.0 && self.next_f64() < probability
+    }
+
+    /// Flips each bit of the byte with given probability.
+    pub(crate) fn flip_bits(&mut self, byte: u8, bit_error_rate: f64) -> u8 {
+        (0..8).fold(byte, |byte, bit| match self.chance(bit_error_rate) {
+            true => byte ^ (1 << bit),
+            false => byte,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SimulationRng;
+
+    #[test]
+    fn test_same_seed_gives_same_sequence() {
+        let mut rng_1 = SimulationRng::new(42);
+        let mut rng_2 = SimulationRng::new(42);
+        let mut rng_3 = SimulationRng::new(43);
+
+        let sequence_1: Vec<u64> = (0..10).map(|_| rng_1.next_u64()).collect();
+        let sequence_2: Vec<u64> = (0..10).map(|_| rng_2.next_u64()).collect();
+        let sequence_3: Vec<u64> = (0..10).map(|_| rng_3.next_u64()).collect();
+
+        assert_eq!(sequence_1, sequence_2);
+        assert_ne!(sequence_1, sequence_3);
+    }
+
+    #[test]
+    fn test_chance_bounds() {
+        let mut rng = SimulationRng::new(0);
+        assert!((0..1000).all(|_| !rng.chance(0.0)));
+        assert!((0..1000).all(|_| rng.chance(1.0)));
+    }
+
+    #[test]
+    fn test_flip_bits_bounds() {
+        let mut rng = SimulationRng::new(0);
+        assert_eq!(rng.flip_bits(0b1010_0101, 0.0), 0b1010_0101);
+        assert_eq!(rng.flip_bits(0b1010_0101, 1.0), 0b0101_1010);
+    }
+}
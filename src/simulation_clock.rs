@@ -0,0 +1,97 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+struct ClockState {
+    ticks: AtomicU64,
+    elapsed_ms: AtomicU64,
+    ms_per_tick: AtomicU64,
+}
+
+/// Simulated time of the network.
+/// The time is not bound to the wall clock, instead it moves forward
+/// by configured amount of milliseconds each time the tick is finished.
+/// So the protocols under test, which are driven by this clock, behave
+/// the same way regardless of the machine load.
+///
+/// Clones share the same time, so the clock can be queried from any thread.
+/// ```
+/// use proto_lab::NetworkSimulator;
+///
+/// let simulator = NetworkSimulator::new(1);
+/// let clock = simulator.get_clock();
+///
+/// simulator.start_tick();
+/// simulator.simulate();
+/// simulator.end_tick();
+///
+/// assert_eq!(clock.get_ticks(), 1);
+/// assert_eq!(clock.get_elapsed_ms(), 1);
+/// ```
+pub struct SimulationClock {
+    state: Arc<ClockState>,
+}
+
+impl SimulationClock {
+    pub(crate) fn new(ms_per_tick: u64) -> Self {
+        Self {
+            state: Arc::new(ClockState {
+                ticks: AtomicU64::new(0),
+                elapsed_ms: AtomicU64::new(0),
+                ms_per_tick: AtomicU64::new(ms_per_tick),
+            }),
+        }
+    }
+
+    /// Returns the count of ticks, which are finished since the start of simulation.
+    pub fn get_ticks(&self) -> u64 {
+        self.state.ticks.load(Ordering::Acquire)
+    }
+
+    /// Returns simulated milliseconds passed since the start of simulation.
+    pub fn get_elapsed_ms(&self) -> u64 {
+        self.state.elapsed_ms.load(Ordering::Acquire)
+    }
+
+    /// Returns how many simulated milliseconds each tick lasts.
+    pub fn get_ms_per_tick(&self) -> u64 {
+        self.state.ms_per_tick.load(Ordering::Acquire)
+    }
+
+    /// Sets how many simulated milliseconds each following tick lasts.
+    /// Already elapsed time is kept, so the clock stays monotonic.
+    /// ```
+    /// use proto_lab::NetworkSimulator;
+    ///
+    /// let simulator = NetworkSimulator::new(1);
+    /// let clock = simulator.get_clock();
+    /// clock.set_ms_per_tick(10);
+    ///
+    /// simulator.start_tick();
+    /// simulator.simulate();
+    /// simulator.end_tick();
+    ///
+    /// assert_eq!(clock.get_elapsed_ms(), 10);
+    /// ```
+    pub fn set_ms_per_tick(&self, ms_per_tick: u64) {
+        self.state.ms_per_tick.store(ms_per_tick, Ordering::Release);
+    }
+
+    /// Moves the simulated time one tick forward.
+    pub(crate) fn advance(&self) {
+        let ms_per_tick = self.get_ms_per_tick();
+        self.state
+            .elapsed_ms
+            .fetch_add(ms_per_tick, Ordering::AcqRel);
+        self.state.ticks.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+impl Clone for SimulationClock {
+    fn clone(&self) -> Self {
+        Self {
+            state: Arc::clone(&self.state),
+        }
+    }
+}
@@ -0,0 +1,398 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    device::{DriverHandle, IODriverSimulator},
+    rng::SimulationRng,
+    RateMismatchPolicy, SimulatorError,
+};
+
+/// Seed of the wire, which was not seeded explicitly.
+const DEFAULT_SEED: u64 = 0;
+
+/// Configuration and state of simulation, shared by all the clones of the wire.
+struct WireState {
+    rng: SimulationRng,
+    bit_error_rate: f64,
+    rate_mismatch_policy: RateMismatchPolicy,
+}
+
+/// Point-to-point full-duplex link, which connects exactly two devices.
+/// Unlike the ether - both ends can transmit at the same tick without
+/// any collision, because each direction has it's own byte stream.
+pub struct WireSimulator {
+    name: String,
+    endpoints: Arc<Mutex<Vec<DriverHandle>>>,
+    /// Devices which take part in the current tick. Devices registered
+    /// or unregistered in the middle of the tick are affected from the next tick.
+    tick_endpoints: Mutex<Option<Vec<DriverHandle>>>,
+    state: Arc<Mutex<WireState>>,
+}
+
+impl WireSimulator {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: String::from(name),
+            endpoints: Arc::new(Mutex::new(vec![])),
+            tick_endpoints: Mutex::new(None),
+            state: Arc::new(Mutex::new(WireState {
+                rng: SimulationRng::new(DEFAULT_SEED),
+                bit_error_rate: 0.0,
+                rate_mismatch_policy: RateMismatchPolicy::default(),
+            })),
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, WireState> {
+        self.state.lock().expect("Fail to get lock on wire state")
+    }
+
+    /// Gets the name of the wire
+    /// ```
+    /// use proto_lab::WireSimulator;
+    /// let wire = WireSimulator::new("my_wire");
+    /// assert_eq!(wire.get_name(), "my_wire");
+    /// ```
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets the probability (0.0 ..= 1.0) for each bit of transferred bytes
+    /// to be flipped, in both directions of the wire.
+    /// ```
+    /// use proto_lab::{IODriverSimulator, WireSimulator, WiredModemFake};
+    ///
+    /// let mut wire = WireSimulator::new("wire");
+    /// let modem_1 = WiredModemFake::new("modem_1");
+    /// let modem_2 = WiredModemFake::new("modem_2");
+    /// wire.connect_driver(modem_1.clone());
+    /// wire.connect_driver(modem_2.clone());
+    ///
+    /// wire.set_bit_error_rate(1.0);
+    /// assert_eq!(wire.get_bit_error_rate(), 1.0);
+    ///
+    /// modem_1.put_to_rx_pin(0b1111_0000);
+    /// wire.start_tick();
+    /// wire.simulate();
+    /// wire.end_tick();
+    /// assert_eq!(modem_2.get_from_tx_pin(), Some(0b0000_1111));
+    /// ```
+    pub fn set_bit_error_rate(&mut self, bit_error_rate: f64) {
+        self.lock_state().bit_error_rate = bit_error_rate;
+    }
+
+    /// Gets the bit error rate of the wire.
+    pub fn get_bit_error_rate(&self) -> f64 {
+        self.lock_state().bit_error_rate
+    }
+
+    /// Sets what happens with the bytes, transferred between the devices
+    /// with different data rates.
+    /// ```
+    /// use proto_lab::{DataRate, IODriverSimulator, RateMismatchPolicy, WireSimulator, WiredModemFake};
+    ///
+    /// let mut wire = WireSimulator::new("wire");
+    /// wire.set_rate_mismatch_policy(RateMismatchPolicy::Drop);
+    /// assert_eq!(wire.get_rate_mismatch_policy(), RateMismatchPolicy::Drop);
+    ///
+    /// let modem_1 = WiredModemFake::new("modem_1");
+    /// let modem_2 = WiredModemFake::new("modem_2");
+    /// modem_2.set_data_rate(DataRate::BytesPerTick(2));
+    /// wire.connect_driver(modem_1.clone());
+    /// wire.connect_driver(modem_2.clone());
+    ///
+    /// modem_1.put_to_rx_pin(b'a');
+    /// wire.start_tick();
+    /// wire.simulate();
+    /// wire.end_tick();
+    /// assert_eq!(modem_2.get_from_tx_pin(), None);
+    /// ```
+    pub fn set_rate_mismatch_policy(&mut self, policy: RateMismatchPolicy) {
+        self.lock_state().rate_mismatch_policy = policy;
+    }
+
+    /// Gets what happens with the bytes, transferred between the devices
+    /// with different data rates.
+    pub fn get_rate_mismatch_policy(&self) -> RateMismatchPolicy {
+        self.lock_state().rate_mismatch_policy
+    }
+
+    /// Re-seeds pseudo-random generator of the wire, which drives all the
+    /// random decisions of the wire.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.lock_state().rng = SimulationRng::new(seed);
+    }
+
+    /// Connects a device to one of the free ends of the wire.
+    /// Panics if both ends of the wire are already connected.
+    /// ```
+    /// use proto_lab::{IODriverSimulator, WireSimulator, WiredModemFake};
+    ///
+    /// let mut wire = WireSimulator::new("my_wire");
+    /// wire.connect_driver(WiredModemFake::new("modem_1"));
+    /// wire.connect_driver(WiredModemFake::new("modem_2"));
+    /// assert_eq!(wire.get_driver("modem_2").unwrap().get_name(), "modem_2");
+    /// ```
+    pub fn connect_driver<D>(&mut self, driver: D)
+    where
+        D: IODriverSimulator + Send + Sync + 'static,
+    {
+        self.connect_driver_handle(Arc::new(driver));
+    }
+
+    /// Connects a device, which is already wrapped into shared handle,
+    /// to one of the free ends of the wire.
+    /// Panics if both ends of the wire are already connected.
+    pub fn connect_driver_handle(&mut self, driver: DriverHandle) {
+        self.try_connect_driver_handle(driver)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Connects a device, which is already wrapped into shared handle,
+    /// to one of the free ends of the wire.
+    /// ```
+    /// use std::sync::Arc;
+    /// use proto_lab::{SimulatorError, WireSimulator, WiredModemFake};
+    ///
+    /// let mut wire = WireSimulator::new("my_wire");
+    /// wire.connect_driver(WiredModemFake::new("modem_1"));
+    /// wire.connect_driver(WiredModemFake::new("modem_2"));
+    /// assert_eq!(
+    ///     wire.try_connect_driver_handle(Arc::new(WiredModemFake::new("modem_3"))),
+    ///     Err(SimulatorError::WireFullyConnected { wire: String::from("my_wire") })
+    /// );
+    /// ```
+    pub fn try_connect_driver_handle(
+        &mut self,
+        driver: DriverHandle,
+    ) -> Result<(), SimulatorError> {
+        let mut endpoints = self
+            .endpoints
+            .lock()
+            .expect("Fail to get lock on endpoints");
+
+        if endpoints.len() >= 2 {
+            return Err(SimulatorError::WireFullyConnected {
+                wire: self.name.clone(),
+            });
+        }
+
+        endpoints.push(driver);
+        Ok(())
+    }
+
+    /// Disconnects a device from the wire, which frees that end of the wire.
+    /// ```
+    /// use proto_lab::{WireSimulator, WiredModemFake};
+    ///
+    /// let mut wire = WireSimulator::new("my_wire");
+    /// wire.connect_driver(WiredModemFake::new("modem_1"));
+    /// wire.connect_driver(WiredModemFake::new("modem_2"));
+    /// wire.disconnect_driver("modem_1");
+    /// assert!(wire.get_driver("modem_1").is_none());
+    /// wire.connect_driver(WiredModemFake::new("modem_3"));
+    /// ```
+    pub fn disconnect_driver(&mut self, name: &str) {
+        let mut endpoints = self
+            .endpoints
+            .lock()
+            .expect("Fail to get lock on endpoints");
+        endpoints.retain(|endpoint| endpoint.get_name() != name);
+    }
+
+    /// Gets a connected device
+    /// ```
+    /// use proto_lab::{IODriverSimulator, WireSimulator, WiredModemFake};
+    ///
+    /// let mut wire = WireSimulator::new("my_wire");
+    /// assert!(wire.get_driver("modem_1").is_none());
+    /// wire.connect_driver(WiredModemFake::new("modem_1"));
+    /// assert_eq!(wire.get_driver("modem_1").unwrap().get_name(), "modem_1");
+    /// ```
+    pub fn get_driver(&self, name: &str) -> Option<DriverHandle> {
+        let endpoints = self
+            .endpoints
+            .lock()
+            .expect("Fail to get lock on endpoints");
+
+        endpoints
+            .iter()
+            .find(|endpoint| endpoint.get_name() == name)
+            .map(Arc::clone)
+    }
+
+    /// Prepares both connected devices for starting of simulation during tick.
+    pub fn start_tick(&self) {
+        let endpoints = self
+            .endpoints
+            .lock()
+            .expect("Fail to get lock on endpoints")
+            .clone();
+        for endpoint in endpoints.iter() {
+            endpoint.start_tick();
+        }
+        self.tick_endpoints
+            .lock()
+            .expect("Fail to get lock on tick endpoints")
+            .replace(endpoints);
+    }
+
+    /// Prepares both connected devices for ending of simulation during tick.
+    pub fn end_tick(&self) {
+        let endpoints = self
+            .tick_endpoints
+            .lock()
+            .expect("Fail to get lock on tick endpoints")
+            .take();
+        let endpoints = endpoints.unwrap_or_else(|| {
+            self.endpoints
+                .lock()
+                .expect("Fail to get lock on endpoints")
+                .clone()
+        });
+        for endpoint in endpoints.iter() {
+            endpoint.end_tick();
+        }
+    }
+
+    /// Returns devices, which are taking part in the current tick.
+    fn get_tick_endpoints(&self) -> Vec<DriverHandle> {
+        let tick_endpoints = self
+            .tick_endpoints
+            .lock()
+            .expect("Fail to get lock on tick endpoints");
+        match tick_endpoints.as_ref() {
+            Some(endpoints) => endpoints.clone(),
+            None => self
+                .endpoints
+                .lock()
+                .expect("Fail to get lock on endpoints")
+                .clone(),
+        }
+    }
+
+    /// Transfers bytes in both directions of the wire.
+    /// This operation shall be called only during tick is active.
+    /// ```
+    /// use proto_lab::{IODriverSimulator, WireSimulator, WiredModemFake};
+    ///
+    /// let mut wire = WireSimulator::new("wire");
+    ///
+    /// let modem_1 = WiredModemFake::new("modem_1");
+    /// let modem_2 = WiredModemFake::new("modem_2");
+    ///
+    /// wire.connect_driver(modem_1.clone());
+    /// wire.connect_driver(modem_2.clone());
+    ///
+    /// modem_1.put_to_rx_pin(b'a');
+    /// modem_2.put_to_rx_pin(b'b');
+    ///
+    /// wire.start_tick();
+    /// wire.simulate();
+    /// wire.end_tick();
+    ///
+    /// assert_eq!(modem_1.get_from_tx_pin(), Some(b'b'));
+    /// assert_eq!(modem_2.get_from_tx_pin(), Some(b'a'));
+    /// ```
+    pub fn simulate(&mut self) {
+        let endpoints = self.get_tick_endpoints();
+
+        if let [end_a, end_b] = endpoints.as_slice() {
+            let from_a = end_a.get_bytes_from_device_network_side();
+            let from_b = end_b.get_bytes_from_device_network_side();
+
+            let mut state = self.lock_state();
+            let state = &mut *state;
+            let mut transfer =
+                |bytes: Vec<u8>, source: &DriverHandle, destination: &DriverHandle| {
+                    if bytes.is_empty() {
+                        return;
+                    }
+                    let Some(bytes) = state.rate_mismatch_policy.transfer(
+                        source.data_rate(),
+                        destination.data_rate(),
+                        bytes,
+                        &mut state.rng,
+                    ) else {
+                        return;
+                    };
+                    let bytes: Vec<u8> = bytes
+                        .into_iter()
+                        .map(|byte| state.rng.flip_bits(byte, state.bit_error_rate))
+                        .collect();
+                    destination.put_bytes_to_device_network_side(&bytes);
+                };
+
+            transfer(from_a, end_a, end_b);
+            transfer(from_b, end_b, end_a);
+        }
+    }
+}
+
+impl Clone for WireSimulator {
+    /// Clones itself.
+    /// Also makes all internal data shared to be able to use from multiple threads.
+    /// ```
+    /// use proto_lab::WireSimulator;
+    ///
+    /// let wire = WireSimulator::new("my_wire");
+    /// let wire_clone = wire.clone();
+    ///
+    /// assert_eq!(wire.get_name(), wire_clone.get_name());
+    /// ```
+    fn clone(&self) -> WireSimulator {
+        WireSimulator {
+            name: String::from(&self.name),
+            endpoints: Arc::clone(&self.endpoints),
+            tick_endpoints: Mutex::new(None),
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::WireSimulator;
+    use crate::{IODriverSimulator, WiredModemFake};
+
+    #[test]
+    fn test_full_duplex_streams_do_not_collide() {
+        let mut wire = WireSimulator::new("wire");
+
+        let modem_1 = WiredModemFake::new("modem_1");
+        let modem_2 = WiredModemFake::new("modem_2");
+
+        wire.connect_driver(modem_1.clone());
+        wire.connect_driver(modem_2.clone());
+
+        for b in b"hello" {
+            modem_1.put_to_rx_pin(*b);
+        }
+        for b in b"world" {
+            modem_2.put_to_rx_pin(*b);
+        }
+
+        for _ in 0..5 {
+            wire.start_tick();
+            wire.simulate();
+            wire.end_tick();
+        }
+
+        let mut received_by_1 = [0u8; 5];
+        let mut received_by_2 = [0u8; 5];
+
+        assert_eq!(modem_1.read(&mut received_by_1), Ok(5));
+        assert_eq!(modem_2.read(&mut received_by_2), Ok(5));
+
+        assert_eq!(&received_by_1, b"world");
+        assert_eq!(&received_by_2, b"hello");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_third_device_can_not_be_connected() {
+        let mut wire = WireSimulator::new("wire");
+        wire.connect_driver(WiredModemFake::new("modem_1"));
+        wire.connect_driver(WiredModemFake::new("modem_2"));
+        wire.connect_driver(WiredModemFake::new("modem_3"));
+    }
+}